@@ -0,0 +1,128 @@
+use std::{
+    hint::black_box,
+    sync::atomic::{AtomicBool, Ordering},
+    thread,
+    time::{Duration, Instant},
+};
+
+use slot_algorithm::{
+    config::PoolConfig,
+    pool::Pool,
+    shared::SharedPool,
+    slots::fruit::{FruitBet, FruitSymbol},
+};
+
+const DRAWS_PER_THREAD: u64 = 200_000;
+const THREADS: [usize; 5] = [1, 2, 4, 8, 16];
+
+fn shared_pool() -> SharedPool {
+    let config = PoolConfig::builder()
+        .id(1)
+        .owner_id(1)
+        .brokerage_ratio(1000)
+        .jackpot_ratio(100)
+        .boundary(100000)
+        .advance(10000)
+        .build()
+        .unwrap();
+    SharedPool::new(Pool::from_config_with_seed(&config, 1).unwrap())
+}
+
+/// 多个线程同时下注，返回总耗时
+fn contend<F: Fn(&SharedPool) + Sync>(threads: usize, draw: F) -> Duration {
+    let pool = shared_pool();
+    let start = Instant::now();
+    thread::scope(|scope| {
+        for _ in 0..threads {
+            let pool = pool.clone();
+            let draw = &draw;
+            scope.spawn(move || {
+                for _ in 0..DRAWS_PER_THREAD {
+                    draw(&pool);
+                }
+            });
+        }
+    });
+    start.elapsed()
+}
+
+fn report(name: &str, threads: usize, ops: u64, elapsed: Duration) {
+    println!(
+        "{:<24} threads {:>2}  {:>12.0} ops/s  {:>8.1} ns/op",
+        name,
+        threads,
+        ops as f64 / elapsed.as_secs_f64(),
+        elapsed.as_nanos() as f64 / ops as f64
+    );
+}
+
+fn main() {
+    for threads in THREADS {
+        let elapsed = contend(threads, |pool| {
+            black_box(pool.draw(1, 2));
+        });
+        report("draw", threads, threads as u64 * DRAWS_PER_THREAD, elapsed);
+    }
+
+    let fruits = vec![
+        FruitBet {
+            symbol: FruitSymbol::Apple,
+            value: 5,
+        },
+        FruitBet {
+            symbol: FruitSymbol::Watermelon,
+            value: 2,
+        },
+    ];
+    for threads in THREADS {
+        let elapsed = contend(threads, |pool| {
+            black_box(pool.draw_fruit(fruits.clone()).unwrap());
+        });
+        report(
+            "draw_fruit",
+            threads,
+            threads as u64 * DRAWS_PER_THREAD,
+            elapsed,
+        );
+    }
+
+    // 一个线程持续下注时，其余线程读取池底、彩金和佣金，读取不加锁
+    for threads in THREADS {
+        let pool = shared_pool();
+        let done = AtomicBool::new(false);
+        let reads = thread::scope(|scope| {
+            let readers: Vec<_> = (0..threads)
+                .map(|_| {
+                    let pool = pool.clone();
+                    let done = &done;
+                    scope.spawn(move || {
+                        let mut reads = 0u64;
+                        while !done.load(Ordering::Relaxed) {
+                            black_box(pool.pot() + pool.jackpot() + pool.brokerage());
+                            reads += 1;
+                        }
+                        reads
+                    })
+                })
+                .collect();
+            let start = Instant::now();
+            for _ in 0..DRAWS_PER_THREAD {
+                black_box(pool.draw(1, 2));
+            }
+            let elapsed = start.elapsed();
+            done.store(true, Ordering::Relaxed);
+            let reads: u64 = readers.into_iter().map(|r| r.join().unwrap()).sum();
+            report("draw with readers", threads, DRAWS_PER_THREAD, elapsed);
+            (reads, elapsed)
+        });
+        report("lock-free reads", threads, reads.0, reads.1);
+    }
+
+    // 对照：同样的读取改为加锁读取快照字段
+    let pool = shared_pool();
+    let start = Instant::now();
+    for _ in 0..DRAWS_PER_THREAD {
+        black_box(pool.with(|pool| pool.pot + pool.jackpot + pool.brokerage));
+    }
+    report("locked reads", 1, DRAWS_PER_THREAD, start.elapsed());
+}
@@ -0,0 +1,40 @@
+use rand::SeedableRng;
+use slot_algorithm::{
+    pool::PoolRng,
+    wave::{self, RatioBounds, WaveParams},
+};
+
+fn main() {
+    let mut rng = PoolRng::seed_from_u64(5);
+    let params = WaveParams::default();
+    let waves = wave::create_wave_with_params(50000, 0, 100000, &params, &mut rng).unwrap();
+    let bounds = RatioBounds {
+        min: 0.1,
+        max: 10.0,
+    };
+    let analysis = wave::analyze(&waves, 50000, 0, 100000, Some(bounds));
+    println!("{:#?}", analysis);
+
+    let flat = wave::analyze(&[50000, 50000, 50000], 50000, 0, 100000, None);
+    println!(
+        "flat: {:?}, degenerate = {}",
+        flat.issues,
+        flat.is_degenerate()
+    );
+
+    // 推进与回撤系数相同且浪数为偶数时系数之和为 0，会产生 NaN
+    let broken = WaveParams {
+        driving_more: vec![1.0],
+        driving_less: vec![1.0],
+        driving_count: 2,
+        ..WaveParams::default()
+    };
+    println!("check: {}", broken.check().unwrap_err());
+    let raw = wave::create_raw_wave_with_params(50000, 0, 100000, &broken, &mut rng).unwrap();
+    let analysis = wave::analyze_raw(&raw, 50000, 0, 100000, None);
+    println!(
+        "broken: {:?}, degenerate = {}",
+        analysis.issues,
+        analysis.is_degenerate()
+    );
+}
@@ -0,0 +1,30 @@
+use slot_algorithm::{config::PoolConfig, pool::Pool};
+
+fn main() {
+    let config = PoolConfig::builder()
+        .id(1)
+        .owner_id(1)
+        .brokerage_ratio(1000)
+        .jackpot_ratio(100)
+        .boundary(100000)
+        .advance(10000)
+        .speed_rate(500)
+        .big_odds(40)
+        .build()
+        .unwrap();
+    let mut pool = Pool::from_config_with_seed(&config, 7).unwrap();
+    println!("{:?}", pool.draw(1, 5));
+
+    // 未填写的速度参数使用默认值
+    let json = r#"{"id":2,"owner_id":1,"bet_unit":1,"brokerage_ratio":500,"jackpot_ratio":100,"boundary":50000}"#;
+    let config: PoolConfig = serde_json::from_str(json).unwrap();
+    config.check().unwrap();
+    println!("{:?}", config);
+
+    let err = PoolConfig::builder()
+        .boundary(100000)
+        .speed_big(10000)
+        .build()
+        .unwrap_err();
+    println!("{}", err);
+}
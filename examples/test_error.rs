@@ -0,0 +1,108 @@
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+use rand::RngCore;
+use slot_algorithm::{
+    error::{PoolError, Result},
+    generators::{RandomWalk, WaveConfig},
+    pool::Pool,
+    slots::fruit::{self, FruitBet, FruitSymbol},
+    wave::WaveGenerator,
+};
+
+/// 只有一段的波浪，生成若干次后失败
+#[derive(Debug)]
+struct Exhausting {
+    remaining: AtomicUsize,
+}
+
+impl WaveGenerator for Exhausting {
+    fn generate(
+        &self,
+        pot: u64,
+        _baseline: u64,
+        boundary: u64,
+        _rng: &mut dyn RngCore,
+    ) -> Result<Vec<u64>> {
+        self.remaining
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+            .map_err(|_| PoolError::InvalidWaveParams("generator exhausted".into()))?;
+        Ok(vec![(pot + boundary) / 2])
+    }
+}
+
+fn main() {
+    // 比率之和超过 RATIO
+    let err = Pool::try_new(1, 1, 1, 9000, 2000, 100000, 10000).unwrap_err();
+    println!("{}", err);
+
+    // 边界线不高于底线
+    let err = Pool::try_load_pool(1, 1, 1, 500, 500, 1000, 100, 500, 0, 0, 0, 0).unwrap_err();
+    println!("{}", err);
+
+    let mut pool = Pool::try_new(1, 1, 1, 1000, 100, 100000, 10000).unwrap();
+    assert_eq!(pool.try_draw(1, 0), Err(PoolError::InvalidOdds(0)));
+    assert_eq!(pool.try_draw(u64::MAX / 2, 100), Err(PoolError::Overflow));
+    assert!(pool.try_update_ratios(6000, 5000).is_err());
+    println!("{:?}", pool.try_draw(1, 2));
+
+    // 水果机整组下注在开奖前检查，溢出时池子状态不变
+    let err = Pool::try_with_seed(1, 1, 1, 9000, 2000, 100000, 10000, 1).unwrap_err();
+    println!("{}", err);
+    let mut pool = Pool::try_with_seed(1, 1, u64::MAX / 1000, 1000, 100, 100000, 10000, 1).unwrap();
+    let snapshot = serde_json::to_string(&pool.snapshot()).unwrap();
+    let bets = vec![
+        FruitBet {
+            symbol: FruitSymbol::Orange,
+            value: 1,
+        },
+        FruitBet {
+            symbol: FruitSymbol::Apple,
+            value: 100,
+        },
+    ];
+    assert_eq!(
+        fruit::try_draw(bets, &mut pool).unwrap_err(),
+        PoolError::Overflow
+    );
+    assert_eq!(serde_json::to_string(&pool.snapshot()).unwrap(), snapshot);
+    let bet = FruitBet {
+        symbol: FruitSymbol::Bar,
+        value: 0,
+    };
+    println!(
+        "{}",
+        fruit::try_draw(vec![bet.clone()], &mut pool).unwrap_err()
+    );
+    // 不检查金额范围的 draw 照常开奖
+    fruit::draw(vec![bet], &mut pool);
+
+    // 下注后生成新波浪失败时，本局的下注也不会记入池子
+    let mut pool = Pool::with_seed(1, 1, 1, 1000, 100, 100000, 10000, 1);
+    pool.set_wave_generator(Arc::new(Exhausting {
+        remaining: AtomicUsize::new(4),
+    }))
+    .unwrap();
+    let err = loop {
+        let snapshot = serde_json::to_string(&pool.snapshot()).unwrap();
+        if let Err(err) = pool.try_draw(100, 2) {
+            assert_eq!(serde_json::to_string(&pool.snapshot()).unwrap(), snapshot);
+            break err;
+        }
+    };
+    println!("{}", err);
+
+    // 快照中的配置不合法时恢复失败，不会静默替换波浪生成器或在下注时溢出
+    let snapshot = Pool::with_seed(1, 1, 1, 1000, 100, 100000, 10000, 1).snapshot();
+    let mut invalid = snapshot.clone();
+    invalid.speed_big = 20000;
+    println!("{}", Pool::restore(invalid).unwrap_err());
+    let mut invalid = snapshot;
+    invalid.wave = Some(WaveConfig::RandomWalk(RandomWalk {
+        steps: 0,
+        ..Default::default()
+    }));
+    println!("{}", Pool::restore(invalid).unwrap_err());
+}
@@ -0,0 +1,57 @@
+use std::sync::Arc;
+
+use rand::{Rng, RngCore};
+use slot_algorithm::{
+    error::Result,
+    pool::{Pool, PoolSnapshot},
+    wave::WaveGenerator,
+};
+
+/// 在底线与边界线之间来回摆动的简单波浪
+#[derive(Debug)]
+struct Zigzag {
+    legs: usize,
+}
+
+impl WaveGenerator for Zigzag {
+    fn generate(
+        &self,
+        pot: u64,
+        baseline: u64,
+        boundary: u64,
+        rng: &mut dyn RngCore,
+    ) -> Result<Vec<u64>> {
+        let mut waves = Vec::with_capacity(self.legs);
+        for leg in 0..self.legs {
+            let target = if leg % 2 == 0 {
+                rng.gen_range(pot..=boundary.max(pot))
+            } else {
+                rng.gen_range(baseline.min(pot)..=pot)
+            };
+            waves.push(target);
+        }
+        Ok(waves)
+    }
+}
+
+fn main() {
+    let mut pool = Pool::with_seed(1, 1, 1, 1000, 100, 100000, 10000, 7);
+    pool.set_wave_generator(Arc::new(Zigzag { legs: 6 }))
+        .unwrap();
+    println!("generator: {:?}", pool.wave_generator());
+    println!("segment: {:?}", pool.get_segment());
+
+    for _ in 0..5000 {
+        pool.draw(1, 2);
+    }
+    println!("pot after 5000 draws: {}", pool.pot);
+
+    let snapshot: PoolSnapshot = pool.snapshot();
+    let mut restored =
+        Pool::restore_with_generator(snapshot, Arc::new(Zigzag { legs: 6 })).unwrap();
+    for _ in 0..5000 {
+        assert_eq!(pool.draw(1, 2), restored.draw(1, 2));
+    }
+    assert_eq!(pool.pot, restored.pot);
+    println!("restored pool with custom generator resumed identically");
+}
@@ -0,0 +1,83 @@
+use slot_algorithm::{
+    config::PoolConfig,
+    generators::{MarkovRegime, RandomWalk, SineWave, WaveConfig},
+    pool::Pool,
+    simulate::{self, BetStrategy, SimConfig},
+    wave::WaveParams,
+};
+
+fn main() {
+    let waves = [
+        WaveConfig::Golden(WaveParams::default()),
+        WaveConfig::RandomWalk(RandomWalk::default()),
+        WaveConfig::Sine(SineWave::default()),
+        WaveConfig::MarkovRegime(MarkovRegime::default()),
+    ];
+    let sim = SimConfig {
+        rounds: 200000,
+        strategy: BetStrategy::Fixed { bets: 1, odds: 2 },
+        seed: 1,
+    };
+
+    for wave in waves {
+        let config = PoolConfig::builder()
+            .id(1)
+            .owner_id(1)
+            .brokerage_ratio(1000)
+            .jackpot_ratio(100)
+            .boundary(100000)
+            .advance(10000)
+            .wave(wave.clone())
+            .build()
+            .unwrap();
+        let mut pool = Pool::from_config_with_seed(&config, 7).unwrap();
+        let report = simulate::run(&mut pool, &sim).unwrap().report();
+        println!(
+            "{:<14} rtp {:.4} hit {:.4} pot [{}, {}]",
+            name(&wave),
+            report.rtp,
+            report.hit_frequency,
+            report.pot_min,
+            report.pot_max
+        );
+
+        let snapshot = pool.snapshot();
+        let mut restored = Pool::restore(snapshot).unwrap();
+        for _ in 0..10000 {
+            assert_eq!(pool.draw(1, 2), restored.draw(1, 2));
+        }
+    }
+
+    let toml = r#"
+        id = 1
+        owner_id = 1
+        bet_unit = 1
+        brokerage_ratio = 1000
+        jackpot_ratio = 100
+        boundary = 100000
+
+        [wave]
+        type = "markov_regime"
+        transitions = [[0.5, 0.5, 0.0], [0.25, 0.5, 0.25], [0.0, 0.5, 0.5]]
+        steps = 32
+    "#;
+    let config: PoolConfig = toml::from_str(toml).unwrap();
+    config.check().unwrap();
+    println!("parsed: {:?}", config.wave);
+
+    let mut broken = MarkovRegime::default();
+    broken.transitions[0] = [0.5, 0.0, 0.0];
+    println!(
+        "broken: {}",
+        WaveConfig::MarkovRegime(broken).check().unwrap_err()
+    );
+}
+
+fn name(wave: &WaveConfig) -> &'static str {
+    match wave {
+        WaveConfig::Golden(_) => "golden",
+        WaveConfig::RandomWalk(_) => "random_walk",
+        WaveConfig::Sine(_) => "sine",
+        WaveConfig::MarkovRegime(_) => "markov_regime",
+    }
+}
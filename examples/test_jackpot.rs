@@ -0,0 +1,66 @@
+use slot_algorithm::{
+    config::PoolConfig,
+    jackpot::{JackpotRule, JackpotTierConfig, JackpotTierKind},
+    pool::Pool,
+};
+
+fn main() {
+    let config = PoolConfig::builder()
+        .id(1)
+        .owner_id(1)
+        .brokerage_ratio(1000)
+        .jackpot_ratio(200)
+        .boundary(100000)
+        .advance(10000)
+        .jackpot_rule(JackpotRule {
+            probability: 5,
+            must_hit_by: 5_000_000,
+            seed: 1_000_000,
+        })
+        .build()
+        .unwrap();
+    let mut pool = Pool::from_config_with_seed(&config, 1).unwrap();
+    println!("initial jackpot = {}", pool.jackpot);
+
+    let mut hits = 0;
+    for round in 0..100000 {
+        let award = pool.draw_with_jackpot(10, 2).jackpot;
+        if let Some(award) = award {
+            hits += 1;
+            println!("round {} jackpot {:?}", round, award);
+        }
+    }
+    println!(
+        "jackpot hits = {}, current jackpot = {}",
+        hits, pool.jackpot
+    );
+
+    // 重置值达到必中线时每次下注都会触发，配置校验时拒绝
+    let err = PoolConfig::builder()
+        .boundary(100000)
+        .jackpot_rule(JackpotRule {
+            probability: 5,
+            must_hit_by: 500_000,
+            seed: 1_000_000,
+        })
+        .build()
+        .unwrap_err();
+    println!("{}", err);
+
+    // 封顶值低于必中线时必中永远不会发生
+    let err = PoolConfig::builder()
+        .boundary(100000)
+        .jackpot_tier(JackpotTierConfig {
+            kind: JackpotTierKind::Mini,
+            share: 5000,
+            ceiling: 400_000,
+            rule: JackpotRule {
+                probability: 5,
+                must_hit_by: 500_000,
+                seed: 100_000,
+            },
+        })
+        .build()
+        .unwrap_err();
+    println!("{}", err);
+}
@@ -0,0 +1,108 @@
+use std::{env, fs, sync::Arc};
+
+use slot_algorithm::{
+    config::PoolConfig,
+    jackpot::JackpotRule,
+    journal::{self, Journal, JournalEvent},
+    pool::{Pool, PoolSnapshot},
+    slots::fruit::{self, FruitBet, FruitSymbol},
+};
+
+fn main() {
+    let config = PoolConfig::builder()
+        .id(1)
+        .owner_id(1)
+        .brokerage_ratio(1000)
+        .jackpot_ratio(200)
+        .boundary(100000)
+        .advance(10000)
+        .jackpot_rule(JackpotRule {
+            probability: 5,
+            must_hit_by: 5_000_000,
+            seed: 1_000_000,
+        })
+        .build()
+        .unwrap();
+    let mut pool = Pool::from_config_with_seed(&config, 7).unwrap();
+
+    let path = env::temp_dir().join("slot-journal.jsonl");
+    let snapshot_path = env::temp_dir().join("slot-journal-snapshot.json");
+    let _ = fs::remove_file(&path);
+    let journal = Arc::new(Journal::open(&path).unwrap());
+    let snapshot = serde_json::to_string(&pool.snapshot()).unwrap();
+    fs::write(&snapshot_path, &snapshot).unwrap();
+    pool.add_observer(journal.clone());
+
+    // 水果机在下注之间也会消耗随机数，回放时按日志中的位置跳过
+    for round in 0..3000 {
+        fruit::draw(
+            vec![FruitBet {
+                symbol: FruitSymbol::Bell,
+                value: 3,
+            }],
+            &mut pool,
+        );
+        pool.draw_with_jackpot(10, 2);
+        pool.draw(1, 5);
+        if round == 1000 {
+            pool.update_ratios(800, 300);
+        }
+        if round == 2000 {
+            pool.inject_advance(5000).unwrap();
+            pool.withdraw_brokerage(pool.brokerage / 2).unwrap();
+        }
+    }
+    assert!(journal.error().is_none());
+    println!(
+        "journal {} entries, verify with: cargo run --bin slot-replay {} {}",
+        journal.seq(),
+        snapshot_path.display(),
+        path.display()
+    );
+
+    let snapshot: PoolSnapshot = serde_json::from_str(&snapshot).unwrap();
+    let entries = journal::read_journal(&path).unwrap();
+    let report = journal::replay(snapshot.clone(), &entries).unwrap();
+    println!(
+        "verified {} entries: {} draws, {} settlements, {} ratio updates",
+        report.verified, report.draws, report.settlements, report.ratio_updates
+    );
+    assert!(report.is_ok());
+    assert_eq!(report.snapshot.pot, pool.pot);
+    assert_eq!(report.snapshot.jackpot, pool.jackpot);
+    assert_eq!(report.snapshot.brokerage, pool.brokerage);
+
+    // 篡改一条命中记录的派奖，回放在该记录处停止
+    let mut tampered = entries.clone();
+    let entry = tampered
+        .iter_mut()
+        .find(|entry| matches!(&entry.event, JournalEvent::Draw { outcome, .. } if outcome.hit))
+        .unwrap();
+    if let JournalEvent::Draw { outcome, .. } = &mut entry.event {
+        outcome.reward += 1;
+    }
+    let report = journal::replay(snapshot.clone(), &tampered).unwrap();
+    let mismatch = report.mismatch.unwrap();
+    println!(
+        "tampered journal rejected at seq {}: {}",
+        mismatch.seq, mismatch.reason
+    );
+
+    // 未中彩金的 draw_with_jackpot 同样按原调用回放，改成普通下注后回放失败
+    let mut tampered = entries;
+    let entry = tampered
+        .iter_mut()
+        .find(|entry| {
+            matches!(&entry.event, JournalEvent::Draw { jackpot: true, outcome, .. } if outcome.jackpot.is_none())
+        })
+        .unwrap();
+    if let JournalEvent::Draw { jackpot, .. } = &mut entry.event {
+        *jackpot = false;
+    }
+    let report = journal::replay(snapshot, &tampered).unwrap();
+    let mismatch = report.mismatch.unwrap();
+    println!(
+        "jackpot draw replayed as plain draw rejected at seq {}: {}",
+        mismatch.seq, mismatch.reason
+    );
+}
@@ -0,0 +1,30 @@
+use slot_algorithm::{money::Credits, pool::Pool};
+
+fn main() {
+    // 每分价值 10
+    let mut pool = Pool::with_seed(1, 1, 10, 1000, 100, 10000000, 100000, 5);
+    for _ in 0..1000 {
+        pool.draw(2, 5);
+    }
+    println!(
+        "suction = {} credits / {} money",
+        pool.suction_credits(),
+        pool.suction_money()
+    );
+    println!(
+        "pot = {} credits / {} money",
+        pool.pot_credits(),
+        pool.pot_money()
+    );
+    println!(
+        "brokerage = {} credits / {} money",
+        pool.brokerage_credits(),
+        pool.brokerage_money()
+    );
+    println!(
+        "jackpot = {} credits / {} money",
+        pool.jackpot_credits(),
+        pool.jackpot_money()
+    );
+    println!("{:?}", Credits(3).to_money(pool.bet_unit));
+}
@@ -0,0 +1,86 @@
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc,
+};
+
+use slot_algorithm::{
+    observer::PoolObserver,
+    pool::{DrawOutcome, Pool},
+    slots::fruit::{self, FruitBet, FruitSymbol},
+};
+
+/// 统计各类事件次数，模拟推送到数据看板
+#[derive(Default)]
+struct Dashboard {
+    bets: AtomicU64,
+    hits: AtomicU64,
+    misses: AtomicU64,
+    refleshes: AtomicU64,
+    segments: AtomicU64,
+    waves: AtomicU64,
+    ratio_updates: AtomicU64,
+}
+
+impl PoolObserver for Dashboard {
+    fn on_bet(&self, _pool: &Pool, bets: u64, _odds: u64) {
+        self.bets.fetch_add(bets, Ordering::Relaxed);
+    }
+
+    fn on_hit(&self, _pool: &Pool, _outcome: &DrawOutcome) {
+        self.hits.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn on_miss(&self, _pool: &Pool, _outcome: &DrawOutcome) {
+        self.misses.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn on_reflesh(&self, _pool: &Pool, _outcome: &DrawOutcome) {
+        self.refleshes.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn on_segment_change(&self, _pool: &Pool, _before: (u64, u64), _after: (u64, u64)) {
+        self.segments.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn on_new_wave(&self, pool: &Pool) {
+        self.waves.fetch_add(1, Ordering::Relaxed);
+        println!("pool {} new wave at pot {}", pool.id, pool.pot);
+    }
+
+    fn on_ratios_updated(&self, pool: &Pool, brokerage_ratio: u64, jackpot_ratio: u64) {
+        self.ratio_updates.fetch_add(1, Ordering::Relaxed);
+        println!(
+            "ratios {}/{} -> {}/{}",
+            brokerage_ratio, jackpot_ratio, pool.brokerage_ratio, pool.jackpot_ratio
+        );
+    }
+}
+
+fn main() {
+    let dashboard = Arc::new(Dashboard::default());
+    let mut pool = Pool::with_seed(1, 1, 1, 1000, 100, 100000, 10000, 5);
+    pool.add_observer(dashboard.clone());
+
+    for _ in 0..5000 {
+        pool.draw(1, 2);
+        fruit::draw(
+            vec![FruitBet {
+                symbol: FruitSymbol::Apple,
+                value: 2,
+            }],
+            &mut pool,
+        );
+    }
+    pool.update_ratios(500, 200);
+
+    println!(
+        "bets = {}, hits = {}, misses = {}, reflesh = {}, segments = {}, waves = {}, ratio updates = {}",
+        dashboard.bets.load(Ordering::Relaxed),
+        dashboard.hits.load(Ordering::Relaxed),
+        dashboard.misses.load(Ordering::Relaxed),
+        dashboard.refleshes.load(Ordering::Relaxed),
+        dashboard.segments.load(Ordering::Relaxed),
+        dashboard.waves.load(Ordering::Relaxed),
+        dashboard.ratio_updates.load(Ordering::Relaxed),
+    );
+}
@@ -0,0 +1,31 @@
+use slot_algorithm::pool::{FallState, Pool};
+
+fn main() {
+    let mut pool = Pool::with_seed(1, 1, 1, 1000, 100, 100000, 10000, 11);
+    let mut hits = 0;
+    let mut refreshes = 0;
+    let mut new_waves = 0;
+    let mut streak = 0;
+    let mut longest = 0;
+    for _ in 0..20000 {
+        let outcome = pool.draw(1, 3);
+        if outcome.hit {
+            hits += 1;
+            streak = 0;
+        } else {
+            streak += 1;
+            longest = longest.max(streak);
+        }
+        if outcome.fall_state == Some(FallState::Reflesh) {
+            refreshes += 1;
+        }
+        if outcome.new_wave {
+            new_waves += 1;
+            println!("{}", serde_json::to_string(&outcome).unwrap());
+        }
+    }
+    println!(
+        "hits = {}, reflesh = {}, new waves = {}, longest miss streak = {}",
+        hits, refreshes, new_waves, longest
+    );
+}
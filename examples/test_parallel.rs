@@ -0,0 +1,79 @@
+use std::time::Instant;
+
+use slot_algorithm::{
+    config::PoolConfig,
+    simulate::{self, BetStrategy, ParallelConfig, SimConfig},
+    slots::fruit::{FruitBet, FruitSymbol},
+};
+
+fn main() {
+    let pool_config = PoolConfig::builder()
+        .id(1)
+        .owner_id(1)
+        .brokerage_ratio(1000)
+        .jackpot_ratio(100)
+        .boundary(100000)
+        .advance(10000)
+        .build()
+        .unwrap();
+    let config = SimConfig {
+        rounds: 100000,
+        strategy: BetStrategy::Fruit {
+            bets: vec![
+                FruitBet {
+                    symbol: FruitSymbol::Apple,
+                    value: 5,
+                },
+                FruitBet {
+                    symbol: FruitSymbol::Watermelon,
+                    value: 2,
+                },
+            ],
+        },
+        seed: 0,
+    };
+    let parallel = ParallelConfig {
+        replicas: 16,
+        threads: 0,
+        seed: 2024,
+    };
+
+    let start = Instant::now();
+    let result = simulate::run_parallel(&pool_config, &config, &parallel).unwrap();
+    println!("elapsed = {:?}", start.elapsed());
+    println!(
+        "rounds = {}, rtp = {:.4} [{:.4}, {:.4}], hit = {:.4} [{:.4}, {:.4}]",
+        result.report.rounds,
+        result.report.rtp,
+        result.rtp_ci.low,
+        result.rtp_ci.high,
+        result.report.hit_frequency,
+        result.hit_frequency_ci.low,
+        result.hit_frequency_ci.high,
+    );
+
+    // 相同主种子的结果与线程数无关
+    let single = simulate::run_parallel(
+        &pool_config,
+        &config,
+        &ParallelConfig {
+            threads: 1,
+            ..parallel
+        },
+    )
+    .unwrap();
+    assert_eq!(single.report.total_win, result.report.total_win);
+
+    // 下注金额不合法时返回错误，不会让工作线程 panic
+    let invalid = SimConfig {
+        strategy: BetStrategy::Fruit {
+            bets: vec![FruitBet {
+                symbol: FruitSymbol::Apple,
+                value: 500,
+            }],
+        },
+        ..config
+    };
+    let err = simulate::run_parallel(&pool_config, &invalid, &parallel).unwrap_err();
+    println!("{}", err);
+}
@@ -0,0 +1,89 @@
+use slot_algorithm::{
+    config::PoolConfig,
+    pool::Pool,
+    rtp::{RtpDivert, RtpTarget},
+    simulate::{self, BetStrategy, SimConfig},
+};
+
+fn main() {
+    let sim = SimConfig {
+        rounds: 2000000,
+        strategy: BetStrategy::RandomOdds {
+            bets: 1,
+            odds: vec![2, 5, 10],
+        },
+        seed: 1,
+    };
+    let builder = || {
+        PoolConfig::builder()
+            .id(1)
+            .owner_id(1)
+            .brokerage_ratio(1000)
+            .jackpot_ratio(100)
+            .boundary(100000)
+            .advance(10000)
+    };
+
+    let mut pool = Pool::from_config_with_seed(&builder().build().unwrap(), 3).unwrap();
+    let baseline = simulate::run(&mut pool, &sim).unwrap().report();
+    println!(
+        "no target: overall rtp {:.4}, pot max {}",
+        baseline.rtp, baseline.pot_max
+    );
+
+    // 调整命中概率：调整量限制在 max_adjust 以内，池底受波浪约束，长期 RTP 仍趋向池底比率
+    for target in [8500, 9200] {
+        let config = builder()
+            .rtp_target(RtpTarget::new(target))
+            .build()
+            .unwrap();
+        let mut pool = Pool::from_config_with_seed(&config, 3).unwrap();
+        let report = simulate::run(&mut pool, &sim).unwrap().report();
+        let rtp = pool.rtp.as_ref().unwrap();
+        println!(
+            "target {} by odds: overall rtp {:.4}, window rtp {:?}, bias {}, in band {}, pot max {}",
+            target,
+            report.rtp,
+            rtp.rtp(),
+            rtp.bias(),
+            rtp.in_band(),
+            report.pot_max
+        );
+        assert!(rtp.bias().unsigned_abs() <= rtp.target().max_adjust);
+    }
+
+    // 分流到彩金：长期 RTP 收敛到目标，池底不超过未启用目标时的范围
+    let mut target = RtpTarget::new(8500);
+    target.divert = RtpDivert::Jackpot;
+    let config = builder().rtp_target(target).build().unwrap();
+    let mut pool = Pool::from_config_with_seed(&config, 3).unwrap();
+    let report = simulate::run(&mut pool, &sim).unwrap().report();
+    let rtp = pool.rtp.as_ref().unwrap();
+    println!(
+        "target 8500 by jackpot: overall rtp {:.4}, window rtp {:?}, bias {}, pot max {}, jackpot contributed {}",
+        report.rtp,
+        rtp.rtp(),
+        rtp.bias(),
+        report.pot_max,
+        report.jackpot_contributed
+    );
+    assert!((report.rtp - 0.85).abs() < 0.01);
+    assert!(report.pot_max <= baseline.pot_max);
+    // 分流不计入佣金
+    assert_eq!(report.brokerage, baseline.brokerage);
+
+    // 分流只能降低 RTP，目标高于池底比率时拒绝
+    let mut target = RtpTarget::new(9500);
+    target.divert = RtpDivert::Jackpot;
+    let err = builder().rtp_target(target).build().unwrap_err();
+    println!("{}", err);
+
+    // 调整比率后池底比率低于分流目标时拒绝，池子不变
+    let mut target = RtpTarget::new(8500);
+    target.divert = RtpDivert::Jackpot;
+    let config = builder().rtp_target(target).build().unwrap();
+    let mut pool = Pool::from_config_with_seed(&config, 3).unwrap();
+    let err = pool.try_update_ratios(2000, 100).unwrap_err();
+    println!("{}", err);
+    assert_eq!(pool.brokerage_ratio, 1000);
+}
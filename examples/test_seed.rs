@@ -0,0 +1,31 @@
+use slot_algorithm::{
+    pool::Pool,
+    slots::fruit::{self, FruitBet, FruitSymbol},
+};
+
+fn bets() -> Vec<FruitBet> {
+    vec![
+        FruitBet {
+            symbol: FruitSymbol::Bar,
+            value: 1,
+        },
+        FruitBet {
+            symbol: FruitSymbol::Apple,
+            value: 3,
+        },
+    ]
+}
+
+fn main() {
+    let mut a = Pool::with_seed(1, 1, 1, 1000, 100, 100000, 10000, 42);
+    let mut b = Pool::with_seed(1, 1, 1, 1000, 100, 100000, 10000, 42);
+
+    for _ in 0..10000 {
+        let ra = fruit::draw(bets(), &mut a);
+        let rb = fruit::draw(bets(), &mut b);
+        assert_eq!(ra.positions, rb.positions);
+    }
+    assert_eq!(a.pot, b.pot);
+    assert_eq!(a.get_segment(), b.get_segment());
+    println!("same seed reproduced the same draws, pot = {}", a.pot);
+}
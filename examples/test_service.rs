@@ -0,0 +1,147 @@
+use std::{env, fs, sync::Arc};
+
+use slot_algorithm::{
+    config::PoolConfig,
+    error::PoolError,
+    observer::PoolObserver,
+    pool::{DrawOutcome, Pool},
+    service::{PoolHandle, PoolService},
+    settlement::SettlementKind,
+    slots::fruit::{FruitBet, FruitSymbol},
+};
+
+const POOLS: u32 = 1000;
+const PLAYERS_PER_POOL: u64 = 4;
+const ROUNDS: u64 = 100;
+
+/// 第一次下注完成时 panic，模拟处理命令过程中出错
+struct Crash;
+
+impl PoolObserver for Crash {
+    fn on_draw(&self, _pool: &Pool, _outcome: &DrawOutcome) {
+        panic!("observer crashed");
+    }
+}
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() {
+    let mut handles = Vec::new();
+    let mut tasks = Vec::new();
+    for id in 1..=POOLS {
+        let config = PoolConfig::builder()
+            .id(id)
+            .owner_id(1)
+            .brokerage_ratio(1000)
+            .jackpot_ratio(100)
+            .boundary(100000)
+            .advance(10000)
+            .build()
+            .unwrap();
+        let pool = Pool::from_config_with_seed(&config, id as u64).unwrap();
+        let service = PoolService {
+            // 只为第一个 Pool 保存最终快照
+            snapshot_path: (id == 1).then(|| env::temp_dir().join("slot-service-1.json")),
+            ..Default::default()
+        };
+        let (handle, task) = service.spawn(pool);
+        handles.push(handle);
+        tasks.push(task);
+    }
+
+    // 每个 Pool 有多个玩家同时下注，所有任务在同一个线程上运行
+    let mut players = Vec::new();
+    for handle in &handles {
+        for player in 0..PLAYERS_PER_POOL {
+            let handle = handle.clone();
+            players.push(tokio::spawn(play(handle, player)));
+        }
+    }
+    let mut bets = 0;
+    for player in players {
+        bets += player.await.unwrap();
+    }
+
+    let first = &handles[0];
+    let settlement = first
+        .settle(SettlementKind::WithdrawBrokerage, 1000)
+        .await
+        .unwrap();
+    println!(
+        "pool {} brokerage {} -> {}",
+        first.id(),
+        settlement.brokerage_before,
+        settlement.brokerage_after
+    );
+
+    // 非法的水果机下注返回错误，服务继续运行
+    let err = first
+        .draw_fruit(vec![FruitBet {
+            symbol: FruitSymbol::Lemon,
+            value: 0,
+        }])
+        .await
+        .unwrap_err();
+    println!("{}", err);
+    first.draw(1, 2).await.unwrap();
+    bets += 1;
+
+    // 关闭后的命令被拒绝，最终快照写入文件
+    let mut suction = 0;
+    for handle in &handles {
+        suction += handle.shutdown().await.unwrap().suction;
+    }
+    assert_eq!(first.draw(1, 2).await, Err(PoolError::ServiceClosed));
+    for task in tasks {
+        task.await.unwrap().unwrap();
+    }
+    assert_eq!(suction, bets);
+    println!(
+        "{} pools, total bets = {}, suction = {}, final snapshot of pool 1 in {}",
+        POOLS,
+        bets,
+        suction,
+        env::temp_dir().join("slot-service-1.json").display()
+    );
+
+    // 处理命令时 panic 的服务不再接受命令，上一次正常的快照保持不变，出错时的快照另存排查
+    let mut pool = Pool::with_seed(POOLS + 1, 1, 1, 1000, 100, 100000, 10000, 1);
+    let path = env::temp_dir().join("slot-service-crash.json");
+    let quarantine = env::temp_dir().join("slot-service-crash.json.poisoned");
+    let _ = fs::remove_file(&quarantine);
+    let good = serde_json::to_string_pretty(&pool.snapshot()).unwrap();
+    fs::write(&path, &good).unwrap();
+    pool.add_observer(Arc::new(Crash));
+    let service = PoolService {
+        snapshot_path: Some(path.clone()),
+        ..Default::default()
+    };
+    let (handle, task) = service.spawn(pool);
+    assert_eq!(handle.draw(1, 2).await, Err(PoolError::ServiceClosed));
+    assert_eq!(task.await.unwrap().unwrap_err(), PoolError::Poisoned);
+    assert_eq!(fs::read_to_string(&path).unwrap(), good);
+    assert!(quarantine.exists());
+    println!(
+        "crashed pool snapshot quarantined in {}",
+        quarantine.display()
+    );
+}
+
+async fn play(handle: PoolHandle, player: u64) -> u64 {
+    let mut bets = 0;
+    for round in 0..ROUNDS {
+        if player.is_multiple_of(2) {
+            handle.draw(1, 2 + round % 5).await.unwrap();
+            bets += 1;
+        } else {
+            handle
+                .draw_fruit(vec![FruitBet {
+                    symbol: FruitSymbol::Lemon,
+                    value: 2,
+                }])
+                .await
+                .unwrap();
+            bets += 2;
+        }
+    }
+    bets
+}
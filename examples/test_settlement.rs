@@ -0,0 +1,22 @@
+use slot_algorithm::pool::Pool;
+
+fn main() {
+    let mut pool = Pool::with_seed(1, 1, 1, 1000, 100, 1000000, 100000, 9);
+    for _ in 0..1000 {
+        pool.draw(10, 2);
+    }
+
+    let brokerage = pool.brokerage;
+    let record = pool.withdraw_brokerage(brokerage).unwrap();
+    println!("{}", serde_json::to_string(&record).unwrap());
+
+    let record = pool.inject_advance(50000).unwrap();
+    println!("{}", serde_json::to_string(&record).unwrap());
+
+    let record = pool.repay_advance(30000).unwrap();
+    println!("{}", serde_json::to_string(&record).unwrap());
+    println!("segment = {:?}", pool.get_segment());
+
+    let err = pool.repay_advance(u64::MAX).unwrap_err();
+    println!("{}", err);
+}
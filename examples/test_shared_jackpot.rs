@@ -0,0 +1,40 @@
+use std::thread;
+
+use slot_algorithm::{
+    jackpot::{JackpotRule, SharedJackpot},
+    pool::Pool,
+};
+
+fn main() {
+    let shared = SharedJackpot::new(JackpotRule {
+        probability: 2,
+        must_hit_by: 0,
+        seed: 1_000_000,
+    });
+
+    let handles: Vec<_> = (1..=4)
+        .map(|id| {
+            let shared = shared.clone();
+            thread::spawn(move || {
+                let mut pool = Pool::with_seed(id, id % 2, 1, 1000, 300, 100000, 10000, id as u64);
+                pool.link_jackpot(shared, 5000).unwrap();
+                let mut awards = Vec::new();
+                for _ in 0..50000 {
+                    if let Some(award) = pool.draw_with_jackpot(10, 2).jackpot {
+                        awards.push(award);
+                    }
+                }
+                (id, awards)
+            })
+        })
+        .collect();
+
+    for handle in handles {
+        let (id, awards) = handle.join().unwrap();
+        println!("pool {} won {:?}", id, awards);
+    }
+    println!("shared jackpot = {}", shared.value());
+    for c in shared.snapshot().contributions {
+        println!("{:?}", c);
+    }
+}
@@ -0,0 +1,68 @@
+use std::thread;
+
+use slot_algorithm::{
+    error::PoolError,
+    pool::Pool,
+    shared::SharedPool,
+    slots::fruit::{FruitBet, FruitSymbol},
+};
+
+fn main() {
+    let pool = SharedPool::new(Pool::with_seed(1, 1, 1, 1000, 100, 100000, 10000, 5));
+
+    // 8 个玩家同时下注，另一个线程不加锁地观察池底
+    let players: Vec<_> = (0..8u64)
+        .map(|player| {
+            let pool = pool.clone();
+            thread::spawn(move || {
+                let mut bets = 0;
+                for round in 0..10000u64 {
+                    if player % 2 == 0 {
+                        pool.draw(player + 1, 2 + round % 5);
+                        bets += player + 1;
+                    } else {
+                        pool.draw_fruit(vec![FruitBet {
+                            symbol: FruitSymbol::Orange,
+                            value: 2,
+                        }])
+                        .unwrap();
+                        bets += 2;
+                    }
+                }
+                bets
+            })
+        })
+        .collect();
+    let watcher = {
+        let pool = pool.clone();
+        thread::spawn(move || {
+            let (mut low, mut high) = (u64::MAX, 0);
+            for _ in 0..100000 {
+                let pot = pool.pot();
+                low = low.min(pot);
+                high = high.max(pot);
+            }
+            (low, high)
+        })
+    };
+
+    let bets: u64 = players.into_iter().map(|p| p.join().unwrap()).sum();
+    let (low, high) = watcher.join().unwrap();
+    println!("watcher saw pot between {} and {}", low, high);
+
+    let pool = pool.try_unwrap().unwrap().unwrap();
+    assert_eq!(pool.suction, bets);
+    println!(
+        "total bets = {}, suction = {}, pot = {}, brokerage = {}",
+        bets, pool.suction, pool.pot, pool.brokerage
+    );
+
+    // 持有锁的线程 panic 后不再继续使用池子
+    let pool = SharedPool::new(Pool::with_seed(2, 1, 1, 1000, 100, 100000, 10000, 5));
+    let poisoner = pool.clone();
+    let _ = thread::spawn(move || poisoner.with(|_| panic!("round aborted"))).join();
+    let err = pool.try_draw(1, 2).unwrap_err();
+    println!("{}", err);
+    assert_eq!(err, PoolError::Poisoned);
+    assert!(pool.try_unwrap().unwrap().is_err());
+}
@@ -0,0 +1,42 @@
+use slot_algorithm::{
+    pool::Pool,
+    simulate::{self, BetStrategy, SimConfig},
+    slots::fruit::{FruitBet, FruitSymbol},
+};
+
+fn main() {
+    let mut pool = Pool::with_seed(1, 1, 1, 1000, 100, 100000, 10000, 11);
+    let config = SimConfig {
+        rounds: 200000,
+        strategy: BetStrategy::RandomOdds {
+            bets: 1,
+            odds: vec![2, 5, 10, 20, 50, 100],
+        },
+        seed: 1,
+    };
+    let report = simulate::run(&mut pool, &config).unwrap().report();
+    println!("{}", serde_json::to_string_pretty(&report).unwrap());
+
+    let mut pool = Pool::with_seed(2, 1, 1, 1000, 100, 100000, 10000, 12);
+    let config = SimConfig {
+        rounds: 100000,
+        strategy: BetStrategy::Fruit {
+            bets: vec![
+                FruitBet {
+                    symbol: FruitSymbol::Apple,
+                    value: 5,
+                },
+                FruitBet {
+                    symbol: FruitSymbol::Bar,
+                    value: 1,
+                },
+            ],
+        },
+        seed: 0,
+    };
+    let report = simulate::run(&mut pool, &config).unwrap().report();
+    println!(
+        "fruit rtp = {:.4}, hit = {:.4}, std = {:.4}",
+        report.rtp, report.hit_frequency, report.std_dev
+    );
+}
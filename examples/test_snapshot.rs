@@ -0,0 +1,22 @@
+use slot_algorithm::pool::{Pool, PoolSnapshot};
+
+fn main() {
+    let mut pool = Pool::new(1, 1, 1, 1000, 100, 100000, 10000);
+    for _ in 0..100 {
+        pool.draw(1, 2);
+    }
+
+    // 保存快照并模拟重启
+    let json = serde_json::to_string(&pool.snapshot()).unwrap();
+    let snapshot: PoolSnapshot = serde_json::from_str(&json).unwrap();
+    let mut restored = Pool::restore(snapshot).unwrap();
+
+    for _ in 0..1000 {
+        let a = pool.draw(1, 5);
+        let b = restored.draw(1, 5);
+        assert_eq!(a, b);
+    }
+    assert_eq!(pool.pot, restored.pot);
+    assert_eq!(pool.get_segment(), restored.get_segment());
+    println!("restored pool resumed identically, pot = {}", restored.pot);
+}
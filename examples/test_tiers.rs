@@ -0,0 +1,61 @@
+use slot_algorithm::{
+    config::PoolConfig,
+    jackpot::{JackpotRule, JackpotTierConfig, JackpotTierKind},
+    pool::{Pool, PoolSnapshot},
+};
+
+fn tier(
+    kind: JackpotTierKind,
+    share: u64,
+    seed: u64,
+    ceiling: u64,
+    probability: u64,
+) -> JackpotTierConfig {
+    JackpotTierConfig {
+        kind,
+        share,
+        ceiling,
+        rule: JackpotRule {
+            probability,
+            must_hit_by: 0,
+            seed,
+        },
+    }
+}
+
+fn main() {
+    let config = PoolConfig::builder()
+        .id(1)
+        .owner_id(1)
+        .brokerage_ratio(1000)
+        .jackpot_ratio(300)
+        .boundary(100000)
+        .advance(10000)
+        .jackpot_tier(tier(JackpotTierKind::Mini, 4000, 100_000, 500_000, 200))
+        .jackpot_tier(tier(JackpotTierKind::Minor, 3000, 500_000, 2_000_000, 50))
+        .jackpot_tier(tier(
+            JackpotTierKind::Major,
+            2000,
+            2_000_000,
+            10_000_000,
+            10,
+        ))
+        .jackpot_tier(tier(JackpotTierKind::Grand, 1000, 10_000_000, 0, 1))
+        .build()
+        .unwrap();
+    let mut pool = Pool::from_config_with_seed(&config, 3).unwrap();
+
+    for _ in 0..100000 {
+        if let Some(award) = pool.draw_with_jackpot(5, 2).jackpot {
+            println!("{:?}", award);
+        }
+    }
+    for tier in &pool.jackpot_tiers {
+        println!("{:?} = {}", tier.kind(), tier.value);
+    }
+
+    // 多级彩金状态随快照保存
+    let json = serde_json::to_string(&pool.snapshot()).unwrap();
+    let snapshot: PoolSnapshot = serde_json::from_str(&json).unwrap();
+    assert_eq!(snapshot.jackpot_tiers, pool.jackpot_tiers);
+}
@@ -0,0 +1,43 @@
+use std::sync::Arc;
+
+use rand::SeedableRng;
+use slot_algorithm::{
+    pool::{Pool, PoolRng},
+    wave::{self, GoldenWave, WaveParams},
+};
+
+fn main() {
+    let mut rng = PoolRng::seed_from_u64(1);
+    let gentle = WaveParams {
+        driving_more: vec![1.0],
+        driving_less: vec![0.382],
+        depth: 2,
+        ..WaveParams::default()
+    };
+    gentle.check().unwrap();
+    let waves = wave::create_wave_with_params(50000, 0, 100000, &gentle, &mut rng).unwrap();
+    println!("gentle wave: {} segments", waves.len());
+
+    let volatile = WaveParams {
+        driving_more: vec![1.0, 1.618, 2.618],
+        driving_less: vec![0.764, 0.9],
+        driving_count: 7,
+        ..WaveParams::default()
+    };
+    let waves = wave::create_wave_with_params(50000, 0, 100000, &volatile, &mut rng).unwrap();
+    println!("volatile wave: {} segments", waves.len());
+
+    let broken = WaveParams {
+        adjustment_less: vec![2.5],
+        ..WaveParams::default()
+    };
+    println!("broken params: {}", broken.check().unwrap_err());
+
+    let mut pool = Pool::with_seed(1, 1, 1, 1000, 100, 100000, 10000, 3);
+    pool.set_wave_generator(Arc::new(GoldenWave::new(volatile).unwrap()))
+        .unwrap();
+    for _ in 0..10000 {
+        pool.draw(1, 2);
+    }
+    println!("pot after 10000 draws: {}", pool.pot);
+}
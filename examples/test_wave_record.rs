@@ -0,0 +1,31 @@
+use slot_algorithm::{
+    generators::{SineWave, WaveConfig},
+    pool::Pool,
+    wave::WaveRecord,
+};
+
+fn main() {
+    let wave = WaveConfig::Sine(SineWave::default());
+    let record = WaveRecord::generate(&wave, 10000, 0, 100000, 42).unwrap();
+
+    let json = record.to_json().unwrap();
+    assert_eq!(WaveRecord::from_json(&json).unwrap(), record);
+    let csv = record.to_csv().unwrap();
+    assert_eq!(WaveRecord::from_csv(&csv).unwrap(), record);
+    println!("{}", csv.lines().take(8).collect::<Vec<_>>().join("\n"));
+
+    // 电子表格导出的节日曲线，只有一列目标值
+    let holiday = "target\n20000\n15000.4\n60000\n30000\n";
+    let holiday = WaveRecord::from_csv(holiday).unwrap();
+    println!("holiday targets: {:?}", holiday.targets);
+
+    let mut pool = Pool::with_seed(1, 1, 1, 1000, 100, 100000, 10000, 1);
+    pool.load_wave(&holiday.targets).unwrap();
+    println!("segment: {:?}", pool.get_segment());
+    println!("next targets: {:?}", pool.peek_targets(3));
+
+    let err = pool.load_wave(&[200000]).unwrap_err();
+    println!("{}", err);
+    let err = WaveRecord::from_csv("target\n100\nabc\n").unwrap_err();
+    println!("{}", err);
+}
@@ -0,0 +1,33 @@
+use slot_algorithm::pool::{Pool, PoolSnapshot};
+
+fn main() {
+    let mut a = Pool::with_seed(1, 1, 1, 1000, 100, 100000, 10000, 9);
+    let mut b = Pool::with_seed(1, 1, 1, 1000, 100, 100000, 10000, 9);
+    println!("pending segments: {}", a.get_waves_len());
+    println!("next targets: {:?}", a.peek_targets(5));
+
+    // 预读只展开波浪，不影响开奖
+    for round in 0..20000 {
+        if round % 100 == 0 {
+            a.peek_targets(50);
+        }
+        assert_eq!(a.draw(1, 3), b.draw(1, 3));
+    }
+    assert_eq!(a.get_segment(), b.get_segment());
+    println!("peeking did not change draws, pot = {}", a.pot);
+
+    // 快照保存尚未展开的浪，恢复后继续展开出相同的目标
+    let json = serde_json::to_string(&a.snapshot()).unwrap();
+    let snapshot: PoolSnapshot = serde_json::from_str(&json).unwrap();
+    println!(
+        "snapshot: {} targets, {} unexpanded legs",
+        snapshot.waves.len(),
+        snapshot.wave_legs.len()
+    );
+    let mut restored = Pool::restore(snapshot).unwrap();
+    assert_eq!(a.peek_targets(100), restored.peek_targets(100));
+    for _ in 0..20000 {
+        assert_eq!(a.draw(1, 3), restored.draw(1, 3));
+    }
+    println!("restored pool resumed identically, pot = {}", restored.pot);
+}
@@ -0,0 +1,98 @@
+use std::{fs, process};
+
+use slot_algorithm::{
+    journal::{self, ReplayReport},
+    pool::PoolSnapshot,
+};
+
+const USAGE: &str =
+    "usage: slot-replay <snapshot.json> <journal.jsonl> [--from SEQ] [--output final.json]";
+
+struct Args {
+    snapshot: String,
+    journal: String,
+    from: u64, // 快照对应的日志序号，只回放之后的记录
+    output: Option<String>,
+}
+
+fn main() {
+    match run() {
+        Ok(true) => {}
+        Ok(false) => process::exit(2),
+        Err(err) => {
+            eprintln!("error: {}", err);
+            process::exit(1);
+        }
+    }
+}
+
+fn run() -> Result<bool, String> {
+    let args = parse_args(std::env::args().skip(1))?;
+    let text =
+        fs::read_to_string(&args.snapshot).map_err(|e| format!("{}: {}", args.snapshot, e))?;
+    let snapshot: PoolSnapshot =
+        serde_json::from_str(&text).map_err(|e| format!("{}: {}", args.snapshot, e))?;
+    let entries: Vec<_> = journal::read_journal(&args.journal)
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .filter(|entry| entry.seq > args.from)
+        .collect();
+
+    let report = journal::replay(snapshot, &entries).map_err(|e| e.to_string())?;
+    print_report(&report, entries.len());
+    if let Some(output) = &args.output {
+        let json = serde_json::to_string_pretty(&report.snapshot).map_err(|e| e.to_string())?;
+        fs::write(output, json).map_err(|e| format!("{}: {}", output, e))?;
+        println!("final snapshot written to {}", output);
+    }
+    Ok(report.is_ok())
+}
+
+fn parse_args<I: Iterator<Item = String>>(mut iter: I) -> Result<Args, String> {
+    let mut files = Vec::new();
+    let mut from = 0;
+    let mut output = None;
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--from" => {
+                let value = iter.next().ok_or("--from requires a value")?;
+                from = value
+                    .parse()
+                    .map_err(|_| format!("--from expects a number, got {}", value))?;
+            }
+            "--output" => output = Some(iter.next().ok_or(USAGE)?),
+            "-h" | "--help" => return Err(USAGE.to_string()),
+            _ if files.len() < 2 && !arg.starts_with('-') => files.push(arg),
+            _ => return Err(format!("unexpected argument {}\n{}", arg, USAGE)),
+        }
+    }
+    let mut files = files.into_iter();
+    Ok(Args {
+        snapshot: files.next().ok_or(USAGE)?,
+        journal: files.next().ok_or(USAGE)?,
+        from,
+        output,
+    })
+}
+
+fn print_report(report: &ReplayReport, entries: usize) {
+    println!("entries:       {}", entries);
+    println!("verified:      {}", report.verified);
+    println!("draws:         {}", report.draws);
+    println!("settlements:   {}", report.settlements);
+    println!("ratio updates: {}", report.ratio_updates);
+    if let Some(seq) = report.last_seq {
+        println!("last seq:      {}", seq);
+    }
+    match &report.mismatch {
+        None => println!("result:        ok"),
+        Some(mismatch) => {
+            println!("result:        mismatch at seq {}", mismatch.seq);
+            println!("reason:        {}", mismatch.reason);
+            println!("expected:      {:?}", mismatch.expected);
+            if let Some(actual) = &mismatch.actual {
+                println!("actual:        {:?}", actual);
+            }
+        }
+    }
+}
@@ -0,0 +1,156 @@
+use std::{env, fs, path::Path, process};
+
+use serde::Deserialize;
+use slot_algorithm::{
+    config::PoolConfig,
+    money::Money,
+    pool::Pool,
+    simulate::{self, ParallelConfig, SimConfig, SimReport},
+};
+
+const USAGE: &str =
+    "usage: slot-sim <config.toml|config.json> [--rounds N] [--seed N] [--output report.json]";
+
+/// 模拟配置文件
+#[derive(Debug, Deserialize)]
+struct SimFile {
+    pool: PoolConfig,
+    simulation: SimConfig,
+    #[serde(default)]
+    seed: Option<u64>, // Pool 的随机种子，不填时使用系统熵源
+    #[serde(default)]
+    parallel: Option<ParallelConfig>, // 填写时以多个副本并行模拟
+}
+
+struct Args {
+    config: String,
+    rounds: Option<u64>,
+    seed: Option<u64>,
+    output: Option<String>,
+}
+
+fn main() {
+    if let Err(err) = run() {
+        eprintln!("error: {}", err);
+        process::exit(1);
+    }
+}
+
+fn run() -> Result<(), String> {
+    let args = parse_args(env::args().skip(1))?;
+    let mut file = load(&args.config)?;
+    if let Some(rounds) = args.rounds {
+        file.simulation.rounds = rounds;
+    }
+    if let Some(seed) = args.seed {
+        file.seed = Some(seed);
+    }
+
+    let report = match &file.parallel {
+        Some(parallel) => {
+            let parallel = ParallelConfig {
+                seed: file.seed.unwrap_or(parallel.seed),
+                ..parallel.clone()
+            };
+            let result = simulate::run_parallel(&file.pool, &file.simulation, &parallel)
+                .map_err(|e| e.to_string())?;
+            println!(
+                "rtp 95% ci: [{:.6}, {:.6}]",
+                result.rtp_ci.low, result.rtp_ci.high
+            );
+            result.report
+        }
+        None => {
+            let mut pool = match file.seed {
+                Some(seed) => Pool::from_config_with_seed(&file.pool, seed),
+                None => Pool::from_config(&file.pool),
+            }
+            .map_err(|e| e.to_string())?;
+            simulate::run(&mut pool, &file.simulation)
+                .map_err(|e| e.to_string())?
+                .report()
+        }
+    };
+
+    print_report(&report, file.pool.bet_unit);
+    if let Some(output) = &args.output {
+        let json = serde_json::to_string_pretty(&report).map_err(|e| e.to_string())?;
+        fs::write(output, json).map_err(|e| format!("{}: {}", output, e))?;
+        println!("report written to {}", output);
+    }
+    Ok(())
+}
+
+fn parse_args<I: Iterator<Item = String>>(mut iter: I) -> Result<Args, String> {
+    let mut config = None;
+    let mut rounds = None;
+    let mut seed = None;
+    let mut output = None;
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--rounds" => rounds = Some(parse_number(iter.next(), "--rounds")?),
+            "--seed" => seed = Some(parse_number(iter.next(), "--seed")?),
+            "--output" => output = Some(iter.next().ok_or(USAGE)?),
+            "-h" | "--help" => return Err(USAGE.to_string()),
+            _ if config.is_none() && !arg.starts_with('-') => config = Some(arg),
+            _ => return Err(format!("unexpected argument {}\n{}", arg, USAGE)),
+        }
+    }
+    Ok(Args {
+        config: config.ok_or(USAGE)?,
+        rounds,
+        seed,
+        output,
+    })
+}
+
+fn parse_number(value: Option<String>, flag: &str) -> Result<u64, String> {
+    let value = value.ok_or_else(|| format!("{} requires a value", flag))?;
+    value
+        .parse()
+        .map_err(|_| format!("{} expects a number, got {}", flag, value))
+}
+
+fn load(path: &str) -> Result<SimFile, String> {
+    let text = fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?;
+    let file: SimFile = match Path::new(path).extension().and_then(|ext| ext.to_str()) {
+        Some("json") => serde_json::from_str(&text).map_err(|e| format!("{}: {}", path, e))?,
+        _ => toml::from_str(&text).map_err(|e| format!("{}: {}", path, e))?,
+    };
+    file.pool.check().map_err(|e| e.to_string())?;
+    file.simulation
+        .strategy
+        .check()
+        .map_err(|e| format!("{}: simulation.strategy: {}", path, e))?;
+    Ok(file)
+}
+
+fn print_report(report: &SimReport, bet_unit: u64) {
+    let credits = |money: u64| Money(money).to_credits(bet_unit);
+    println!("rounds:              {}", report.rounds);
+    println!("total bet:           {}", report.total_bet);
+    println!("total win:           {}", report.total_win);
+    println!("rtp:                 {:.6}", report.rtp);
+    println!("rtp with jackpot:    {:.6}", report.rtp_with_jackpot);
+    println!("hit frequency:       {:.6}", report.hit_frequency);
+    println!("std dev:             {:.6}", report.std_dev);
+    println!("pot min:             {}", credits(report.pot_min));
+    println!("pot max:             {}", credits(report.pot_max));
+    println!("pot max drawdown:    {}", credits(report.max_drawdown));
+    println!("brokerage:           {}", credits(report.brokerage));
+    println!(
+        "jackpot contributed: {}",
+        credits(report.jackpot_contributed)
+    );
+    println!(
+        "jackpot paid:        {} ({} hits)",
+        report.jackpot_paid, report.jackpot_hits
+    );
+    println!("wins by odds:");
+    for (odds, stats) in &report.wins_by_odds {
+        println!(
+            "  x{:<5} draws {:>10} hits {:>10} win {:>12}",
+            odds, stats.draws, stats.hits, stats.win
+        );
+    }
+}
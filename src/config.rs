@@ -0,0 +1,246 @@
+use serde::{Deserialize, Serialize};
+use validator::{Validate, ValidationError};
+
+use crate::{
+    error::{PoolError, Result},
+    generators::WaveConfig,
+    jackpot::{self, JackpotRule, JackpotTierConfig},
+    pool::RATIO,
+    rtp::RtpTarget,
+};
+
+pub const DEFAULT_ASCENT_SPEED_RATE: u64 = 2000;
+pub const DEFAULT_SPEED_RATE: u64 = 1000;
+pub const DEFAULT_SPEED_BIG: u64 = 8000;
+pub const DEFAULT_BIG_ODDS: u64 = 50;
+
+/// Pool 的配置，包含比率、边界以及命中松紧度相关的速度参数
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Validate)]
+#[validate(schema(function = "validate_pool_config"))]
+pub struct PoolConfig {
+    pub id: u32,       // ID
+    pub owner_id: u32, // 所有者 ID
+    #[validate(range(min = 1, message = "Bet unit must be positive"))]
+    pub bet_unit: u64, // 每分价值
+    #[validate(range(max = RATIO, message = "Brokerage ratio must not exceed RATIO"))]
+    pub brokerage_ratio: u64, // 佣金比率
+    #[validate(range(max = RATIO, message = "Jackpot ratio must not exceed RATIO"))]
+    pub jackpot_ratio: u64, // 彩金比率
+    #[serde(default)]
+    pub base_line: u64, // 底线
+    pub boundary: u64, // 边界线
+    #[serde(default)]
+    pub advance: u64, // 垫分
+    #[serde(default = "default_ascent_speed_rate")]
+    #[validate(range(max = RATIO, message = "Ascent speed rate must not exceed RATIO"))]
+    pub ascent_speed_rate: u64, // 上升时的减速比率
+    #[serde(default = "default_speed_rate")]
+    #[validate(range(
+        exclusive_max = RATIO,
+        message = "Speed rate must be less than RATIO"
+    ))]
+    pub speed_rate: u64, // 下降时的加速比率
+    #[serde(default = "default_speed_big")]
+    #[validate(range(
+        exclusive_max = RATIO,
+        message = "Big speed rate must be less than RATIO"
+    ))]
+    pub speed_big: u64, // 下降时大赔率的加速比率
+    #[serde(default = "default_big_odds")]
+    #[validate(range(min = 1, message = "Big odds must be positive"))]
+    pub big_odds: u64, // 大赔率的分界
+    #[serde(default)]
+    #[validate(nested)]
+    pub jackpot_rule: Option<JackpotRule>, // 彩金规则，None 表示彩金只累积不派发
+    #[serde(default)]
+    #[validate(nested)]
+    pub jackpot_tiers: Vec<JackpotTierConfig>, // 多级彩金，按份额分走彩金贡献
+    #[serde(default)]
+    pub wave: WaveConfig, // 波浪生成器
+    #[serde(default)]
+    #[validate(nested)]
+    pub rtp_target: Option<RtpTarget>, // 目标 RTP，None 表示不启用 RTP 控制
+}
+
+impl Default for PoolConfig {
+    fn default() -> Self {
+        Self {
+            id: 0,
+            owner_id: 0,
+            bet_unit: 1,
+            brokerage_ratio: 0,
+            jackpot_ratio: 0,
+            base_line: 0,
+            boundary: 0,
+            advance: 0,
+            ascent_speed_rate: DEFAULT_ASCENT_SPEED_RATE,
+            speed_rate: DEFAULT_SPEED_RATE,
+            speed_big: DEFAULT_SPEED_BIG,
+            big_odds: DEFAULT_BIG_ODDS,
+            jackpot_rule: None,
+            jackpot_tiers: Vec::new(),
+            wave: WaveConfig::default(),
+            rtp_target: None,
+        }
+    }
+}
+
+impl PoolConfig {
+    pub fn builder() -> PoolConfigBuilder {
+        PoolConfigBuilder::default()
+    }
+
+    /// 校验配置，比率、边界与波浪参数错误返回对应的错误，其余返回 [`PoolError::InvalidConfig`]
+    pub fn check(&self) -> Result<()> {
+        if self.brokerage_ratio.saturating_add(self.jackpot_ratio) > RATIO {
+            return Err(PoolError::InvalidRatios {
+                brokerage_ratio: self.brokerage_ratio,
+                jackpot_ratio: self.jackpot_ratio,
+            });
+        }
+        if self.boundary <= self.base_line {
+            return Err(PoolError::InvalidBoundary {
+                base_line: self.base_line,
+                boundary: self.boundary,
+            });
+        }
+        self.wave.check()?;
+        self.validate()
+            .map_err(|e| PoolError::InvalidConfig(e.to_string()))
+    }
+
+    /// 池底比率，即扣除佣金与彩金后剩余的比率
+    pub fn pot_ratio(&self) -> u64 {
+        RATIO.saturating_sub(self.brokerage_ratio.saturating_add(self.jackpot_ratio))
+    }
+}
+
+fn validate_pool_config(config: &PoolConfig) -> std::result::Result<(), ValidationError> {
+    if config.brokerage_ratio.saturating_add(config.jackpot_ratio) > RATIO {
+        let mut err = ValidationError::new("ratios");
+        err.message = Some("Brokerage ratio plus jackpot ratio must not exceed RATIO".into());
+        return Err(err);
+    }
+    if config.boundary <= config.base_line {
+        let mut err = ValidationError::new("boundary");
+        err.message = Some("Boundary must be greater than base line".into());
+        return Err(err);
+    }
+    if let Some(rtp) = &config.rtp_target {
+        if !rtp.fits_pot_ratio(config.pot_ratio()) {
+            let mut err = ValidationError::new("rtp_target");
+            err.message = Some("Diverted target RTP must not exceed the pot ratio".into());
+            return Err(err);
+        }
+    }
+    jackpot::validate_tiers(&config.jackpot_tiers)
+}
+
+pub(crate) fn default_ascent_speed_rate() -> u64 {
+    DEFAULT_ASCENT_SPEED_RATE
+}
+
+pub(crate) fn default_speed_rate() -> u64 {
+    DEFAULT_SPEED_RATE
+}
+
+pub(crate) fn default_speed_big() -> u64 {
+    DEFAULT_SPEED_BIG
+}
+
+pub(crate) fn default_big_odds() -> u64 {
+    DEFAULT_BIG_ODDS
+}
+
+/// [`PoolConfig`] 的构建器，`build` 时统一校验
+#[derive(Debug, Clone, Default)]
+pub struct PoolConfigBuilder {
+    config: PoolConfig,
+}
+
+impl PoolConfigBuilder {
+    pub fn id(mut self, id: u32) -> Self {
+        self.config.id = id;
+        self
+    }
+
+    pub fn owner_id(mut self, owner_id: u32) -> Self {
+        self.config.owner_id = owner_id;
+        self
+    }
+
+    pub fn bet_unit(mut self, bet_unit: u64) -> Self {
+        self.config.bet_unit = bet_unit;
+        self
+    }
+
+    pub fn brokerage_ratio(mut self, brokerage_ratio: u64) -> Self {
+        self.config.brokerage_ratio = brokerage_ratio;
+        self
+    }
+
+    pub fn jackpot_ratio(mut self, jackpot_ratio: u64) -> Self {
+        self.config.jackpot_ratio = jackpot_ratio;
+        self
+    }
+
+    pub fn base_line(mut self, base_line: u64) -> Self {
+        self.config.base_line = base_line;
+        self
+    }
+
+    pub fn boundary(mut self, boundary: u64) -> Self {
+        self.config.boundary = boundary;
+        self
+    }
+
+    pub fn advance(mut self, advance: u64) -> Self {
+        self.config.advance = advance;
+        self
+    }
+
+    pub fn ascent_speed_rate(mut self, ascent_speed_rate: u64) -> Self {
+        self.config.ascent_speed_rate = ascent_speed_rate;
+        self
+    }
+
+    pub fn speed_rate(mut self, speed_rate: u64) -> Self {
+        self.config.speed_rate = speed_rate;
+        self
+    }
+
+    pub fn speed_big(mut self, speed_big: u64) -> Self {
+        self.config.speed_big = speed_big;
+        self
+    }
+
+    pub fn big_odds(mut self, big_odds: u64) -> Self {
+        self.config.big_odds = big_odds;
+        self
+    }
+
+    pub fn jackpot_rule(mut self, jackpot_rule: JackpotRule) -> Self {
+        self.config.jackpot_rule = Some(jackpot_rule);
+        self
+    }
+
+    pub fn jackpot_tier(mut self, jackpot_tier: JackpotTierConfig) -> Self {
+        self.config.jackpot_tiers.push(jackpot_tier);
+        self
+    }
+
+    pub fn wave(mut self, wave: WaveConfig) -> Self {
+        self.config.wave = wave;
+        self
+    }
+
+    pub fn rtp_target(mut self, rtp_target: RtpTarget) -> Self {
+        self.config.rtp_target = Some(rtp_target);
+        self
+    }
+
+    pub fn build(self) -> Result<PoolConfig> {
+        self.config.check()?;
+        Ok(self.config)
+    }
+}
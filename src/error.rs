@@ -0,0 +1,102 @@
+use std::fmt;
+
+/// Pool 及波浪生成过程中可能出现的错误
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PoolError {
+    /// 佣金比率与彩金比率之和超过 RATIO
+    InvalidRatios {
+        brokerage_ratio: u64,
+        jackpot_ratio: u64,
+    },
+    /// 边界线必须高于底线
+    InvalidBoundary { base_line: u64, boundary: u64 },
+    /// 池底既不能上升也不能下降，无法生成波浪
+    EmptyWaveRange {
+        pot: u64,
+        base_line: u64,
+        boundary: u64,
+    },
+    /// 各级彩金与联动彩金的份额之和超过 RATIO
+    InvalidJackpotShare(u64),
+    /// 配置校验失败
+    InvalidConfig(String),
+    /// 波浪参数校验失败
+    InvalidWaveParams(String),
+    /// 波浪数据无法解析或超出底线与边界线
+    InvalidWaveData(String),
+    /// 审计日志读写失败或无法解析
+    InvalidJournal(String),
+    /// 文件读写失败
+    Io(String),
+    /// Pool 服务已关闭
+    ServiceClosed,
+    /// 可用金额不足
+    InsufficientFunds { requested: u64, available: u64 },
+    /// 赔率必须大于 0
+    InvalidOdds(u64),
+    /// 下注不合法
+    InvalidBet(String),
+    /// 数值运算溢出
+    Overflow,
+    /// 下注过程中 panic，池子状态可能只更新了一半
+    Poisoned,
+}
+
+impl fmt::Display for PoolError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PoolError::InvalidRatios {
+                brokerage_ratio,
+                jackpot_ratio,
+            } => write!(
+                f,
+                "brokerage ratio {} plus jackpot ratio {} exceeds the ratio base",
+                brokerage_ratio, jackpot_ratio
+            ),
+            PoolError::InvalidBoundary {
+                base_line,
+                boundary,
+            } => write!(
+                f,
+                "boundary {} must be greater than base line {}",
+                boundary, base_line
+            ),
+            PoolError::EmptyWaveRange {
+                pot,
+                base_line,
+                boundary,
+            } => write!(
+                f,
+                "cannot create a wave from pot {} between base line {} and boundary {}",
+                pot, base_line, boundary
+            ),
+            PoolError::InvalidJackpotShare(share) => {
+                write!(f, "jackpot shares {} exceed the ratio base", share)
+            }
+            PoolError::InvalidConfig(reason) => write!(f, "invalid pool config: {}", reason),
+            PoolError::InvalidWaveParams(reason) => {
+                write!(f, "invalid wave params: {}", reason)
+            }
+            PoolError::InvalidWaveData(reason) => write!(f, "invalid wave data: {}", reason),
+            PoolError::InvalidJournal(reason) => write!(f, "invalid journal: {}", reason),
+            PoolError::Io(reason) => write!(f, "io error: {}", reason),
+            PoolError::ServiceClosed => write!(f, "pool service is closed"),
+            PoolError::InsufficientFunds {
+                requested,
+                available,
+            } => write!(
+                f,
+                "insufficient funds: requested {}, available {}",
+                requested, available
+            ),
+            PoolError::InvalidOdds(odds) => write!(f, "odds must be positive, got {}", odds),
+            PoolError::InvalidBet(reason) => write!(f, "invalid bet: {}", reason),
+            PoolError::Overflow => write!(f, "arithmetic overflow"),
+            PoolError::Poisoned => write!(f, "pool lock poisoned by a panicked round"),
+        }
+    }
+}
+
+impl std::error::Error for PoolError {}
+
+pub type Result<T> = std::result::Result<T, PoolError>;
@@ -0,0 +1,319 @@
+use std::{
+    f64::consts::{PI, TAU},
+    sync::Arc,
+};
+
+use rand::{Rng, RngCore};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::{PoolError, Result},
+    wave::{GoldenWave, WaveGenerator, WaveParams, MAX_SEGMENTS},
+};
+
+/// 波浪生成器的配置，用于在配置文件中选择生成器，默认为黄金分割波浪
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum WaveConfig {
+    Golden(WaveParams),         // 黄金分割波浪
+    RandomWalk(RandomWalk),     // 均值回归的有界随机游走
+    Sine(SineWave),             // 带扰动的正弦波
+    MarkovRegime(MarkovRegime), // 冷热阶段的马尔可夫模型
+}
+
+impl Default for WaveConfig {
+    fn default() -> Self {
+        WaveConfig::Golden(WaveParams::default())
+    }
+}
+
+impl WaveConfig {
+    pub fn check(&self) -> Result<()> {
+        match self {
+            WaveConfig::Golden(params) => params.check(),
+            WaveConfig::RandomWalk(walk) => walk.check(),
+            WaveConfig::Sine(sine) => sine.check(),
+            WaveConfig::MarkovRegime(markov) => markov.check(),
+        }
+    }
+
+    /// 按配置创建生成器，参数不合法时返回错误
+    pub fn build(&self) -> Result<Arc<dyn WaveGenerator>> {
+        self.check()?;
+        Ok(match self {
+            WaveConfig::Golden(params) => Arc::new(GoldenWave::new(params.clone())?),
+            WaveConfig::RandomWalk(walk) => Arc::new(walk.clone()),
+            WaveConfig::Sine(sine) => Arc::new(sine.clone()),
+            WaveConfig::MarkovRegime(markov) => Arc::new(markov.clone()),
+        })
+    }
+}
+
+/// 在底线与边界线之间向中线回归的随机游走
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RandomWalk {
+    pub steps: usize,   // 每条波浪的分段数
+    pub step: f64,      // 单步最大幅度，占底线到边界线距离的比例
+    pub reversion: f64, // 每步向中线回归的比例
+}
+
+impl Default for RandomWalk {
+    fn default() -> Self {
+        Self {
+            steps: 64,
+            step: 0.1,
+            reversion: 0.1,
+        }
+    }
+}
+
+impl RandomWalk {
+    pub fn check(&self) -> Result<()> {
+        check_steps("steps", self.steps)?;
+        check_fraction("step", self.step, false)?;
+        check_fraction("reversion", self.reversion, true)
+    }
+}
+
+impl WaveGenerator for RandomWalk {
+    fn generate(
+        &self,
+        pot: u64,
+        baseline: u64,
+        boundary: u64,
+        rng: &mut dyn RngCore,
+    ) -> Result<Vec<u64>> {
+        self.check()?;
+        let (low, high) = bounds(pot, baseline, boundary)?;
+        let mid = (low + high) / 2.0;
+        let width = high - low;
+        let mut level = pot as f64;
+        let mut waves = Vec::with_capacity(self.steps);
+        for _ in 0..self.steps {
+            let shock = rng.gen_range(-1.0..=1.0) * self.step * width;
+            level = (level + (mid - level) * self.reversion + shock).clamp(low, high);
+            waves.push(level.round() as u64);
+        }
+        Ok(waves)
+    }
+}
+
+/// 周期与振幅带随机扰动的正弦波，以底线和边界线的中线为轴
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SineWave {
+    pub cycles: usize,        // 每条波浪的周期数
+    pub points: usize,        // 每个周期的分段数
+    pub amplitude: f64,       // 振幅，占底线到边界线距离一半的比例
+    pub period_noise: f64,    // 周期的随机扰动比例
+    pub amplitude_noise: f64, // 振幅的随机扰动比例
+}
+
+impl Default for SineWave {
+    fn default() -> Self {
+        Self {
+            cycles: 4,
+            points: 8,
+            amplitude: 0.8,
+            period_noise: 0.2,
+            amplitude_noise: 0.2,
+        }
+    }
+}
+
+impl SineWave {
+    pub fn check(&self) -> Result<()> {
+        if self.cycles == 0 || self.points < 2 {
+            return Err(PoolError::InvalidWaveParams(String::from(
+                "sine wave needs at least one cycle of two points",
+            )));
+        }
+        check_steps("cycles * points", self.cycles.saturating_mul(self.points))?;
+        check_fraction("amplitude", self.amplitude, false)?;
+        check_fraction("period_noise", self.period_noise, true)?;
+        check_fraction("amplitude_noise", self.amplitude_noise, true)
+    }
+}
+
+impl WaveGenerator for SineWave {
+    fn generate(
+        &self,
+        pot: u64,
+        baseline: u64,
+        boundary: u64,
+        rng: &mut dyn RngCore,
+    ) -> Result<Vec<u64>> {
+        self.check()?;
+        let (low, high) = bounds(pot, baseline, boundary)?;
+        let mid = (low + high) / 2.0;
+        let half = (high - low) / 2.0;
+
+        // 从池底当前位置所在的相位出发，随机决定先上升还是先下降
+        let offset = ((pot as f64 - mid) / (half * self.amplitude)).clamp(-1.0, 1.0);
+        let mut phase = offset.asin();
+        if rng.gen::<bool>() {
+            phase = PI - phase;
+        }
+
+        let mut waves = Vec::with_capacity(self.cycles * self.points);
+        for _ in 0..self.cycles {
+            let period =
+                self.points as f64 * (1.0 + rng.gen_range(-self.period_noise..=self.period_noise));
+            let amplitude = half
+                * self.amplitude
+                * (1.0 + rng.gen_range(-self.amplitude_noise..=self.amplitude_noise));
+            for _ in 0..self.points {
+                phase += TAU / period;
+                let level = (mid + amplitude * phase.sin()).clamp(low, high);
+                waves.push(level.round() as u64);
+            }
+        }
+        Ok(waves)
+    }
+}
+
+/// 马尔可夫模型中的阶段
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Regime {
+    Hot,     // 热，池底下降，集中派奖
+    Neutral, // 平，池底小幅震荡
+    Cold,    // 冷，池底上升，持续吸分
+}
+
+impl Regime {
+    const ALL: [Regime; 3] = [Regime::Hot, Regime::Neutral, Regime::Cold];
+
+    fn index(self) -> usize {
+        match self {
+            Regime::Hot => 0,
+            Regime::Neutral => 1,
+            Regime::Cold => 2,
+        }
+    }
+}
+
+/// 冷热阶段按转移矩阵切换的马尔可夫模型，每个分段先切换阶段再按阶段移动池底目标
+///
+/// 池底触及底线时强制转为冷阶段，触及边界线时强制转为热阶段
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MarkovRegime {
+    pub transitions: [[f64; 3]; 3], // 状态转移矩阵，行列按热、平、冷排列，每行之和为 1
+    pub initial: Regime,            // 每条波浪开始时的阶段
+    pub steps: usize,               // 每条波浪的分段数
+    pub step: f64,                  // 冷热阶段的单步平均幅度，占底线到边界线距离的比例
+}
+
+impl Default for MarkovRegime {
+    fn default() -> Self {
+        Self {
+            transitions: [[0.6, 0.3, 0.1], [0.2, 0.6, 0.2], [0.1, 0.3, 0.6]],
+            initial: Regime::Neutral,
+            steps: 64,
+            step: 0.05,
+        }
+    }
+}
+
+impl MarkovRegime {
+    pub fn check(&self) -> Result<()> {
+        check_steps("steps", self.steps)?;
+        check_fraction("step", self.step, false)?;
+        for (row, probabilities) in self.transitions.iter().enumerate() {
+            let valid = probabilities.iter().all(|p| p.is_finite() && *p >= 0.0);
+            let sum: f64 = probabilities.iter().sum();
+            if !valid || (sum - 1.0).abs() > 1e-6 {
+                return Err(PoolError::InvalidWaveParams(format!(
+                    "transition row {} must be non-negative and sum to 1, got {:?}",
+                    row, probabilities
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    fn next<R: Rng + ?Sized>(&self, regime: Regime, rng: &mut R) -> Regime {
+        let mut rand = rng.gen::<f64>();
+        for (next, p) in Regime::ALL.iter().zip(self.transitions[regime.index()]) {
+            if rand < p {
+                return *next;
+            }
+            rand -= p;
+        }
+        regime
+    }
+}
+
+impl WaveGenerator for MarkovRegime {
+    fn generate(
+        &self,
+        pot: u64,
+        baseline: u64,
+        boundary: u64,
+        rng: &mut dyn RngCore,
+    ) -> Result<Vec<u64>> {
+        self.check()?;
+        let (low, high) = bounds(pot, baseline, boundary)?;
+        let width = high - low;
+        let mut level = pot as f64;
+        let mut regime = self.initial;
+        let mut waves = Vec::with_capacity(self.steps);
+        for _ in 0..self.steps {
+            regime = if level <= low {
+                Regime::Cold
+            } else if level >= high {
+                Regime::Hot
+            } else {
+                self.next(regime, rng)
+            };
+            let size = self.step * width;
+            let delta = match regime {
+                Regime::Hot => -size * rng.gen_range(0.5..=1.5),
+                Regime::Neutral => size * rng.gen_range(-0.5..=0.5),
+                Regime::Cold => size * rng.gen_range(0.5..=1.5),
+            };
+            level = (level + delta).clamp(low, high);
+            waves.push(level.round() as u64);
+        }
+        Ok(waves)
+    }
+}
+
+/// 底线与边界线，两者之间没有空间时返回错误
+fn bounds(pot: u64, baseline: u64, boundary: u64) -> Result<(f64, f64)> {
+    if boundary <= baseline {
+        return Err(PoolError::EmptyWaveRange {
+            pot,
+            base_line: baseline,
+            boundary,
+        });
+    }
+    Ok((baseline as f64, boundary as f64))
+}
+
+fn check_steps(name: &str, steps: usize) -> Result<()> {
+    if steps == 0 || steps > MAX_SEGMENTS {
+        return Err(PoolError::InvalidWaveParams(format!(
+            "{} must be between 1 and {}, got {}",
+            name, MAX_SEGMENTS, steps
+        )));
+    }
+    Ok(())
+}
+
+/// 比例必须在 (0, 1] 内，`allow_zero` 为 true 时为 [0, 1)
+fn check_fraction(name: &str, value: f64, allow_zero: bool) -> Result<()> {
+    let valid = if allow_zero {
+        (0.0..1.0).contains(&value)
+    } else {
+        value > 0.0 && value <= 1.0
+    };
+    if !valid {
+        return Err(PoolError::InvalidWaveParams(format!(
+            "{} out of range: {}",
+            name, value
+        )));
+    }
+    Ok(())
+}
@@ -0,0 +1,337 @@
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use validator::{Validate, ValidationError};
+
+use crate::{money::Money, pool::RATIO};
+
+pub const PROBABILITY_BASE: u64 = 1_000_000; // 概率 百万分比
+
+/// 彩金触发规则
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Validate)]
+#[validate(schema(function = "validate_jackpot_rule"))]
+pub struct JackpotRule {
+    #[validate(range(
+        max = PROBABILITY_BASE,
+        message = "Probability must not exceed PROBABILITY_BASE"
+    ))]
+    pub probability: u64, // 每分下注的触发概率，百万分比，按下注额放大
+    #[serde(default)]
+    pub must_hit_by: u64, // 必中线，彩金达到该值时必定触发，0 表示不启用
+    #[serde(default)]
+    pub seed: u64, // 最低彩金，派彩后以此重置
+}
+
+impl JackpotRule {
+    /// 判断本次下注是否触发彩金
+    pub fn triggered<R: Rng + ?Sized>(&self, jackpot: u64, bets: u64, rng: &mut R) -> bool {
+        if jackpot == 0 || bets == 0 {
+            return false;
+        }
+        if self.must_hit_by > 0 && jackpot >= self.must_hit_by {
+            return true;
+        }
+        let chance = self.probability.saturating_mul(bets).min(PROBABILITY_BASE);
+        chance > 0 && rng.gen_range(0..PROBABILITY_BASE) < chance
+    }
+
+    /// 派发彩金，返回派彩结果；不足一分的余数留在重置后的彩金中
+    pub fn award(&self, jackpot: u64, bet_unit: u64) -> JackpotAward {
+        let jackpot = Money(jackpot);
+        let raw_amount = jackpot.to_credits(bet_unit).0;
+        let fraction = jackpot.fraction(bet_unit).0;
+        let amount = jackpot.0 - fraction;
+        let reseed = self.seed.saturating_add(fraction);
+        JackpotAward {
+            tier: None,
+            linked: false,
+            amount,
+            raw_amount,
+            reseed,
+        }
+    }
+}
+
+/// 重置后的彩金不能达到必中线，否则之后每次下注都会触发
+fn validate_jackpot_rule(rule: &JackpotRule) -> Result<(), ValidationError> {
+    if rule.must_hit_by > 0 && rule.seed >= rule.must_hit_by {
+        let mut err = ValidationError::new("seed");
+        err.message = Some("Seed must be less than must_hit_by".into());
+        return Err(err);
+    }
+    Ok(())
+}
+
+/// 彩金派彩结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JackpotAward {
+    pub tier: Option<JackpotTierKind>, // 中得的彩金等级，None 表示 Pool::jackpot
+    #[serde(default)]
+    pub linked: bool, // 是否为联动彩金
+    pub amount: u64,                   // 派发的彩金，与 Pool::jackpot 同为万分比单位
+    pub raw_amount: u64,               // 派发的彩金，下注单位
+    pub reseed: u64,                   // 派彩后重置的彩金
+}
+
+/// 多级彩金的等级
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum JackpotTierKind {
+    Mini,
+    Minor,
+    Major,
+    Grand,
+}
+
+/// 单级彩金的配置
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Validate)]
+#[validate(schema(function = "validate_tier_config"))]
+pub struct JackpotTierConfig {
+    pub kind: JackpotTierKind, // 等级
+    #[validate(range(max = RATIO, message = "Share must not exceed RATIO"))]
+    pub share: u64, // 占彩金贡献的份额，万分比
+    #[serde(default)]
+    pub ceiling: u64, // 封顶值，超出部分留在 Pool::jackpot，0 表示不封顶
+    #[serde(flatten)]
+    #[validate(nested)]
+    pub rule: JackpotRule, // 触发规则及重置值
+}
+
+fn validate_tier_config(config: &JackpotTierConfig) -> Result<(), ValidationError> {
+    if config.ceiling > 0 && config.rule.seed > config.ceiling {
+        let mut err = ValidationError::new("ceiling");
+        err.message = Some("Seed must not exceed ceiling".into());
+        return Err(err);
+    }
+    // 封顶值低于必中线时彩金永远达不到必中线
+    if config.ceiling > 0 && config.rule.must_hit_by > config.ceiling {
+        let mut err = ValidationError::new("ceiling");
+        err.message = Some("Must hit value must not exceed ceiling".into());
+        return Err(err);
+    }
+    Ok(())
+}
+
+/// 单级彩金的状态
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JackpotTier {
+    pub config: JackpotTierConfig,
+    pub value: u64, // 当前彩金，万分比单位
+}
+
+impl JackpotTier {
+    pub fn new(config: JackpotTierConfig) -> Self {
+        let value = config.rule.seed;
+        Self { config, value }
+    }
+
+    pub fn kind(&self) -> JackpotTierKind {
+        self.config.kind
+    }
+
+    /// 判定本级彩金是否触发，触发时派发并以种子值重置
+    pub(crate) fn draw<R: Rng + ?Sized>(
+        &mut self,
+        bets: u64,
+        bet_unit: u64,
+        rng: &mut R,
+    ) -> Option<JackpotAward> {
+        if !self.config.rule.triggered(self.value, bets, rng) {
+            return None;
+        }
+        let mut award = self.config.rule.award(self.value, bet_unit);
+        award.tier = Some(self.config.kind);
+        self.value = award.reseed;
+        Some(award)
+    }
+
+    /// 计算加入贡献后的彩金值，返回新值与封顶溢出的部分
+    fn add(&self, contribution: u64) -> Option<(u64, u64)> {
+        let value = self.value.checked_add(contribution)?;
+        match self.config.ceiling {
+            0 => Some((value, 0)),
+            ceiling if value > ceiling => Some((ceiling, value - ceiling)),
+            _ => Some((value, 0)),
+        }
+    }
+}
+
+/// 各级彩金的份额之和
+pub(crate) fn tiers_share(tiers: &[JackpotTier]) -> u64 {
+    tiers
+        .iter()
+        .fold(0u64, |acc, tier| acc.saturating_add(tier.config.share))
+}
+
+/// 按份额将彩金贡献分配到各级彩金，返回各级新值与未分配的部分（含封顶溢出）
+pub(crate) fn split_contribution(
+    tiers: &[JackpotTier],
+    contribution: u64,
+) -> Option<(Vec<u64>, u64)> {
+    let mut values = Vec::with_capacity(tiers.len());
+    let mut rest = contribution;
+    for tier in tiers {
+        let part = contribution.checked_mul(tier.config.share)? / RATIO;
+        let (value, overflow) = tier.add(part)?;
+        values.push(value);
+        rest = rest - part + overflow;
+    }
+    Some((values, rest))
+}
+
+/// 检查各级彩金的份额之和不超过 RATIO 且等级不重复
+pub(crate) fn validate_tiers(tiers: &[JackpotTierConfig]) -> Result<(), ValidationError> {
+    let total = tiers
+        .iter()
+        .fold(0u64, |acc, tier| acc.saturating_add(tier.share));
+    if total > RATIO {
+        let mut err = ValidationError::new("jackpot_tiers");
+        err.message = Some("Jackpot tier shares must not exceed RATIO".into());
+        return Err(err);
+    }
+    let mut kinds: Vec<JackpotTierKind> = tiers.iter().map(|tier| tier.kind).collect();
+    kinds.sort();
+    kinds.dedup();
+    if kinds.len() != tiers.len() {
+        let mut err = ValidationError::new("jackpot_tiers");
+        err.message = Some("Jackpot tier kinds must be unique".into());
+        return Err(err);
+    }
+    Ok(())
+}
+
+/// 单个 Pool 对联动彩金的贡献及中奖记录
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JackpotContribution {
+    pub owner_id: u32,    // 所有者 ID
+    pub pool_id: u32,     // Pool ID
+    pub contributed: u64, // 累计贡献，万分比单位
+    pub won: u64,         // 累计中得，万分比单位
+    pub hits: u64,        // 中奖次数
+}
+
+/// 联动彩金的状态，可序列化保存
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SharedJackpotState {
+    pub rule: JackpotRule,                       // 触发规则及重置值
+    pub value: u64,                              // 当前彩金，万分比单位
+    pub contributions: Vec<JackpotContribution>, // 各 Pool 的贡献记录
+}
+
+impl SharedJackpotState {
+    fn entry(&mut self, owner_id: u32, pool_id: u32) -> &mut JackpotContribution {
+        let index = match self
+            .contributions
+            .iter()
+            .position(|c| c.owner_id == owner_id && c.pool_id == pool_id)
+        {
+            Some(index) => index,
+            None => {
+                self.contributions.push(JackpotContribution {
+                    owner_id,
+                    pool_id,
+                    ..Default::default()
+                });
+                self.contributions.len() - 1
+            }
+        };
+        &mut self.contributions[index]
+    }
+}
+
+/// 多个 Pool 共享的联动彩金，克隆得到的句柄指向同一份彩金，可跨线程使用
+#[derive(Debug, Clone)]
+pub struct SharedJackpot {
+    state: Arc<Mutex<SharedJackpotState>>,
+}
+
+impl SharedJackpot {
+    pub fn new(rule: JackpotRule) -> Self {
+        let value = rule.seed;
+        Self::restore(SharedJackpotState {
+            rule,
+            value,
+            contributions: Vec::new(),
+        })
+    }
+
+    /// 从保存的状态恢复联动彩金
+    pub fn restore(state: SharedJackpotState) -> Self {
+        Self {
+            state: Arc::new(Mutex::new(state)),
+        }
+    }
+
+    pub fn snapshot(&self) -> SharedJackpotState {
+        self.lock().clone()
+    }
+
+    /// 当前彩金
+    pub fn value(&self) -> u64 {
+        self.lock().value
+    }
+
+    /// 指定 Pool 的贡献记录
+    pub fn contribution(&self, owner_id: u32, pool_id: u32) -> Option<JackpotContribution> {
+        self.lock()
+            .contributions
+            .iter()
+            .find(|c| c.owner_id == owner_id && c.pool_id == pool_id)
+            .cloned()
+    }
+
+    /// 检查一笔贡献能否记入，不修改彩金
+    pub(crate) fn can_contribute(&self, owner_id: u32, pool_id: u32, amount: u64) -> bool {
+        let state = self.lock();
+        let contributed = state
+            .contributions
+            .iter()
+            .find(|c| c.owner_id == owner_id && c.pool_id == pool_id)
+            .map_or(0, |c| c.contributed);
+        state.value.checked_add(amount).is_some() && contributed.checked_add(amount).is_some()
+    }
+
+    /// 记入一笔贡献，溢出时不做任何修改并返回 None
+    pub(crate) fn contribute(&self, owner_id: u32, pool_id: u32, amount: u64) -> Option<()> {
+        let mut state = self.lock();
+        let value = state.value.checked_add(amount)?;
+        let entry = state.entry(owner_id, pool_id);
+        entry.contributed = entry.contributed.checked_add(amount)?;
+        state.value = value;
+        Some(())
+    }
+
+    /// 判定联动彩金是否触发，触发时派发给该 Pool 并以种子值重置
+    pub(crate) fn draw<R: Rng + ?Sized>(
+        &self,
+        owner_id: u32,
+        pool_id: u32,
+        bets: u64,
+        bet_unit: u64,
+        rng: &mut R,
+    ) -> Option<JackpotAward> {
+        let mut state = self.lock();
+        if !state.rule.triggered(state.value, bets, rng) {
+            return None;
+        }
+        let mut award = state.rule.award(state.value, bet_unit);
+        award.linked = true;
+        state.value = award.reseed;
+        let entry = state.entry(owner_id, pool_id);
+        entry.won = entry.won.saturating_add(award.amount);
+        entry.hits += 1;
+        Some(award)
+    }
+
+    fn lock(&self) -> MutexGuard<'_, SharedJackpotState> {
+        // 持有锁的线程 panic 不会破坏彩金状态，继续使用
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Pool 与联动彩金的连接
+#[derive(Debug, Clone)]
+pub struct JackpotLink {
+    pub jackpot: SharedJackpot, // 联动彩金
+    pub share: u64,             // 占彩金贡献的份额，万分比
+}
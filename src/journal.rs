@@ -0,0 +1,353 @@
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{BufRead, BufReader, Write},
+    path::Path,
+    sync::{Mutex, MutexGuard},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::{PoolError, Result},
+    observer::PoolObserver,
+    pool::{DrawOutcome, Pool, PoolSnapshot},
+    settlement::{Settlement, SettlementKind},
+};
+
+/// 审计日志中的一条记录，日志文件每行一条 JSON
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JournalEntry {
+    pub seq: u64,            // 序号，从 1 开始连续递增
+    pub pool_id: u32,        // Pool ID
+    pub timestamp: u64,      // 记录时间，Unix 秒
+    pub event: JournalEvent, // 事件
+}
+
+/// 审计日志记录的事件
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum JournalEvent {
+    /// 一次下注，`word_pos` 为下注前随机数生成器的位置，`jackpot` 表示通过 draw_with_jackpot 下注
+    Draw {
+        word_pos: u128,
+        jackpot: bool,
+        outcome: DrawOutcome,
+    },
+    /// 更新佣金比率与彩金比率，记录更新后的比率
+    Ratios {
+        brokerage_ratio: u64,
+        jackpot_ratio: u64,
+    },
+    /// 一次结算，`word_pos` 为结算前随机数生成器的位置
+    Settlement {
+        word_pos: u128,
+        settlement: Settlement,
+    },
+}
+
+/// 只追加的审计日志，作为 [`PoolObserver`] 注册到 Pool 上，记录每次下注、结算及比率更新
+///
+/// 每条记录写入后立即 flush。回调无法返回错误，写入失败时记录第一个错误并停止写入，
+/// 通过 [`Journal::error`] 检查。载入波浪、切换波浪生成器或目标 RTP 等操作不会记录，
+/// 执行后应保存新的快照，并记下当时的 [`Journal::seq`] 作为回放的起点
+pub struct Journal {
+    state: Mutex<JournalState>,
+}
+
+struct JournalState {
+    writer: Box<dyn Write + Send>,
+    seq: u64,                 // 最后一条记录的序号
+    word_pos: Option<u128>,   // 当前下注或结算开始前随机数生成器的位置
+    error: Option<PoolError>, // 第一个写入错误
+}
+
+impl Journal {
+    /// 写入任意输出，`seq` 为已有的最后一条记录的序号，新日志为 0
+    pub fn new<W: Write + Send + 'static>(writer: W, seq: u64) -> Self {
+        Journal {
+            state: Mutex::new(JournalState {
+                writer: Box::new(writer),
+                seq,
+                word_pos: None,
+                error: None,
+            }),
+        }
+    }
+
+    /// 以追加方式打开日志文件，文件已存在时从最后一条记录的序号继续
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let seq = match fs::metadata(path) {
+            Ok(_) => read_journal(path)?.last().map_or(0, |entry| entry.seq),
+            Err(_) => 0,
+        };
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|e| journal_error(path, e))?;
+        Ok(Journal::new(file, seq))
+    }
+
+    /// 最后一条记录的序号
+    pub fn seq(&self) -> u64 {
+        self.lock().seq
+    }
+
+    /// 第一个写入错误，出错后不再写入新的记录
+    pub fn error(&self) -> Option<PoolError> {
+        self.lock().error.clone()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, JournalState> {
+        // 持有锁的线程 panic 时最多丢失一条记录，序号与错误状态仍然有效
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn begin(&self, pool: &Pool) {
+        self.lock().word_pos = Some(pool.rng_word_pos());
+    }
+
+    fn append(&self, pool: &Pool, event: impl FnOnce(Option<u128>) -> JournalEvent) {
+        let mut state = self.lock();
+        if state.error.is_some() {
+            return;
+        }
+        let word_pos = state.word_pos.take();
+        let entry = JournalEntry {
+            seq: state.seq + 1,
+            pool_id: pool.id,
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0),
+            event: event(word_pos),
+        };
+        let written = serde_json::to_string(&entry)
+            .map_err(|e| PoolError::InvalidJournal(e.to_string()))
+            .and_then(|mut line| {
+                line.push('\n');
+                state
+                    .writer
+                    .write_all(line.as_bytes())
+                    .and_then(|_| state.writer.flush())
+                    .map_err(|e| PoolError::InvalidJournal(e.to_string()))
+            });
+        match written {
+            Ok(()) => state.seq = entry.seq,
+            Err(err) => state.error = Some(err),
+        }
+    }
+}
+
+impl PoolObserver for Journal {
+    fn on_bet(&self, pool: &Pool, _bets: u64, _odds: u64) {
+        // 下注计入池底不消耗随机数，此时的位置即为下注前的位置
+        self.begin(pool);
+    }
+
+    fn on_draw(&self, pool: &Pool, outcome: &DrawOutcome) {
+        self.append(pool, |word_pos| JournalEvent::Draw {
+            word_pos: word_pos.unwrap_or_default(),
+            jackpot: outcome.jackpot_drawn,
+            outcome: outcome.clone(),
+        });
+    }
+
+    fn on_settle(&self, pool: &Pool, _kind: SettlementKind, _amount: u64) {
+        self.begin(pool);
+    }
+
+    fn on_settlement(&self, pool: &Pool, settlement: &Settlement) {
+        self.append(pool, |word_pos| JournalEvent::Settlement {
+            word_pos: word_pos.unwrap_or_default(),
+            settlement: settlement.clone(),
+        });
+    }
+
+    fn on_ratios_updated(&self, pool: &Pool, _brokerage_ratio: u64, _jackpot_ratio: u64) {
+        self.append(pool, |_| JournalEvent::Ratios {
+            brokerage_ratio: pool.brokerage_ratio,
+            jackpot_ratio: pool.jackpot_ratio,
+        });
+    }
+}
+
+/// 读取日志文件中的全部记录
+pub fn read_journal<P: AsRef<Path>>(path: P) -> Result<Vec<JournalEntry>> {
+    let path = path.as_ref();
+    let file = File::open(path).map_err(|e| journal_error(path, e))?;
+    let mut entries = Vec::new();
+    for (line_no, line) in BufReader::new(file).lines().enumerate() {
+        let line = line.map_err(|e| journal_error(path, e))?;
+        if let Some(entry) = parse_line(&line, line_no + 1)? {
+            entries.push(entry);
+        }
+    }
+    Ok(entries)
+}
+
+/// 解析 JSON lines 格式的日志，空行忽略
+pub fn parse_journal(text: &str) -> Result<Vec<JournalEntry>> {
+    let mut entries = Vec::new();
+    for (line_no, line) in text.lines().enumerate() {
+        if let Some(entry) = parse_line(line, line_no + 1)? {
+            entries.push(entry);
+        }
+    }
+    Ok(entries)
+}
+
+fn parse_line(line: &str, line_no: usize) -> Result<Option<JournalEntry>> {
+    if line.trim().is_empty() {
+        return Ok(None);
+    }
+    serde_json::from_str(line)
+        .map(Some)
+        .map_err(|e| PoolError::InvalidJournal(format!("line {}: {}", line_no, e)))
+}
+
+fn journal_error(path: &Path, err: std::io::Error) -> PoolError {
+    PoolError::InvalidJournal(format!("{}: {}", path.display(), err))
+}
+
+/// 回放中第一处与日志不一致的记录
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReplayMismatch {
+    pub seq: u64,                     // 记录序号
+    pub reason: String,               // 不一致的原因
+    pub expected: JournalEvent,       // 日志中的事件
+    pub actual: Option<JournalEvent>, // 回放得到的事件，回放出错时为 None
+}
+
+/// 回放结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplayReport {
+    pub verified: usize,                  // 验证一致的记录数
+    pub draws: usize,                     // 其中的下注数
+    pub settlements: usize,               // 其中的结算数
+    pub ratio_updates: usize,             // 其中的比率更新数
+    pub last_seq: Option<u64>,            // 最后一条验证一致的记录序号
+    pub mismatch: Option<ReplayMismatch>, // 第一处不一致，回放在此停止
+    pub snapshot: PoolSnapshot,           // 回放结束时的 Pool 状态
+}
+
+impl ReplayReport {
+    pub fn is_ok(&self) -> bool {
+        self.mismatch.is_none()
+    }
+}
+
+/// 从快照出发按日志逐条重放，验证每次下注和结算的结果与日志完全一致
+///
+/// `entries` 应为快照之后的记录，序号必须连续。每次下注和结算前将随机数生成器设置到日志记录的位置，
+/// 因此下注之间由调用方消耗的随机数（如水果机的开奖位置）不影响回放；随机数位置倒退视为不一致。
+/// 联动彩金不在快照中，联动彩金的派彩无法回放。快照本身无法恢复时返回错误
+pub fn replay(snapshot: PoolSnapshot, entries: &[JournalEntry]) -> Result<ReplayReport> {
+    let mut pool = Pool::restore(snapshot)?;
+    let mut report = ReplayReport {
+        verified: 0,
+        draws: 0,
+        settlements: 0,
+        ratio_updates: 0,
+        last_seq: None,
+        mismatch: None,
+        snapshot: pool.snapshot(),
+    };
+    for entry in entries {
+        if let Err(mismatch) = replay_entry(&mut pool, entry, report.last_seq) {
+            report.mismatch = Some(*mismatch);
+            break;
+        }
+        match entry.event {
+            JournalEvent::Draw { .. } => report.draws += 1,
+            JournalEvent::Settlement { .. } => report.settlements += 1,
+            JournalEvent::Ratios { .. } => report.ratio_updates += 1,
+        }
+        report.verified += 1;
+        report.last_seq = Some(entry.seq);
+    }
+    report.snapshot = pool.snapshot();
+    Ok(report)
+}
+
+fn replay_entry(
+    pool: &mut Pool,
+    entry: &JournalEntry,
+    last_seq: Option<u64>,
+) -> std::result::Result<(), Box<ReplayMismatch>> {
+    let mismatch = |reason: String, actual: Option<JournalEvent>| {
+        Box::new(ReplayMismatch {
+            seq: entry.seq,
+            reason,
+            expected: entry.event.clone(),
+            actual,
+        })
+    };
+    if let Some(last) = last_seq {
+        if entry.seq != last + 1 {
+            return Err(mismatch(format!("expected seq {}", last + 1), None));
+        }
+    }
+    if entry.pool_id != pool.id {
+        return Err(mismatch(format!("expected pool {}", pool.id), None));
+    }
+
+    let actual = match &entry.event {
+        JournalEvent::Draw {
+            word_pos,
+            jackpot,
+            outcome,
+        } => {
+            seek(pool, *word_pos).map_err(|reason| mismatch(reason, None))?;
+            let result = match jackpot {
+                true => pool.try_draw_with_jackpot(outcome.bets, outcome.odds),
+                false => pool.try_draw(outcome.bets, outcome.odds),
+            };
+            let actual = result.map_err(|e| mismatch(e.to_string(), None))?;
+            JournalEvent::Draw {
+                word_pos: *word_pos,
+                jackpot: *jackpot,
+                outcome: actual,
+            }
+        }
+        JournalEvent::Settlement {
+            word_pos,
+            settlement,
+        } => {
+            seek(pool, *word_pos).map_err(|reason| mismatch(reason, None))?;
+            let result = pool.settle(settlement.kind, settlement.amount);
+            let mut actual = result.map_err(|e| mismatch(e.to_string(), None))?;
+            actual.timestamp = settlement.timestamp;
+            JournalEvent::Settlement {
+                word_pos: *word_pos,
+                settlement: actual,
+            }
+        }
+        JournalEvent::Ratios {
+            brokerage_ratio,
+            jackpot_ratio,
+        } => {
+            pool.try_update_ratios(*brokerage_ratio, *jackpot_ratio)
+                .map_err(|e| mismatch(e.to_string(), None))?;
+            entry.event.clone()
+        }
+    };
+    if actual != entry.event {
+        return Err(mismatch(String::from("outcome differs"), Some(actual)));
+    }
+    Ok(())
+}
+
+/// 将随机数生成器移动到日志记录的位置，只允许前进
+fn seek(pool: &mut Pool, word_pos: u128) -> std::result::Result<(), String> {
+    let current = pool.rng_word_pos();
+    if word_pos < current {
+        return Err(format!(
+            "rng position {} is behind replayed position {}",
+            word_pos, current
+        ));
+    }
+    pool.get_mut_rng().set_word_pos(word_pos);
+    Ok(())
+}
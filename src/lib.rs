@@ -1,3 +1,18 @@
+pub mod config;
+pub mod error;
+pub mod generators;
+pub mod jackpot;
+pub mod journal;
+pub mod money;
+pub mod observer;
+#[cfg(feature = "plot")]
+pub mod plot;
 pub mod pool;
+pub mod rtp;
+#[cfg(feature = "tokio")]
+pub mod service;
+pub mod settlement;
+pub mod shared;
+pub mod simulate;
 pub mod slots;
 pub mod wave;
@@ -0,0 +1,98 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::pool::RATIO;
+
+/// 下注单位的金额（分），即玩家下注、派奖及吸码量使用的单位
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(transparent)]
+pub struct Credits(pub u64);
+
+/// 池子内部的定点金额，1 分 = `bet_unit * RATIO`，池底、佣金、彩金及垫分使用该单位
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(transparent)]
+pub struct Money(pub u64);
+
+impl Credits {
+    /// 按每分价值换算为定点金额，溢出时返回 None
+    pub fn to_money(self, bet_unit: u64) -> Option<Money> {
+        self.0
+            .checked_mul(bet_unit)
+            .and_then(|value| value.checked_mul(RATIO))
+            .map(Money)
+    }
+
+    pub fn checked_add(self, other: Credits) -> Option<Credits> {
+        self.0.checked_add(other.0).map(Credits)
+    }
+
+    pub fn checked_sub(self, other: Credits) -> Option<Credits> {
+        self.0.checked_sub(other.0).map(Credits)
+    }
+}
+
+impl Money {
+    /// 每分对应的定点金额
+    pub fn unit(bet_unit: u64) -> u64 {
+        bet_unit.saturating_mul(RATIO)
+    }
+
+    /// 按每分价值换算为下注单位，不足一分的部分舍去
+    pub fn to_credits(self, bet_unit: u64) -> Credits {
+        match Money::unit(bet_unit) {
+            0 => Credits(0),
+            unit => Credits(self.0 / unit),
+        }
+    }
+
+    /// 换算为下注单位后不足一分的余数
+    pub fn fraction(self, bet_unit: u64) -> Money {
+        match Money::unit(bet_unit) {
+            0 => self,
+            unit => Money(self.0 % unit),
+        }
+    }
+
+    /// 按比率（万分比）折算，溢出时返回 None
+    pub fn scale(self, ratio: u64) -> Option<Money> {
+        self.0.checked_mul(ratio).map(|value| Money(value / RATIO))
+    }
+
+    pub fn checked_add(self, other: Money) -> Option<Money> {
+        self.0.checked_add(other.0).map(Money)
+    }
+
+    pub fn checked_sub(self, other: Money) -> Option<Money> {
+        self.0.checked_sub(other.0).map(Money)
+    }
+}
+
+impl fmt::Display for Credits {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// 输出定点金额的原始值，换算为分需要每分价值，见 [`Money::to_credits`]
+impl fmt::Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl From<Credits> for u64 {
+    fn from(credits: Credits) -> u64 {
+        credits.0
+    }
+}
+
+impl From<Money> for u64 {
+    fn from(money: Money) -> u64 {
+        money.0
+    }
+}
@@ -0,0 +1,73 @@
+use std::{fmt, sync::Arc};
+
+use crate::{
+    jackpot::JackpotAward,
+    pool::{DrawOutcome, Pool},
+    settlement::{Settlement, SettlementKind},
+};
+
+/// Pool 生命周期事件的观察者，所有方法默认不做任何事，按需实现
+///
+/// 回调在 Pool 的方法内同步执行，传入的 Pool 已反映事件发生后的状态，回调中应尽快返回
+pub trait PoolObserver: Send + Sync {
+    /// 下注已计入池底、佣金和彩金
+    fn on_bet(&self, _pool: &Pool, _bets: u64, _odds: u64) {}
+
+    /// 下注命中
+    fn on_hit(&self, _pool: &Pool, _outcome: &DrawOutcome) {}
+
+    /// 下注未命中
+    fn on_miss(&self, _pool: &Pool, _outcome: &DrawOutcome) {}
+
+    /// 池底不足以派奖，走 [`FallState::Reflesh`](crate::pool::FallState::Reflesh) 分支切换分段
+    fn on_reflesh(&self, _pool: &Pool, _outcome: &DrawOutcome) {}
+
+    /// 分段发生变化
+    fn on_segment_change(&self, _pool: &Pool, _before: (u64, u64), _after: (u64, u64)) {}
+
+    /// 生成了新的波浪
+    fn on_new_wave(&self, _pool: &Pool) {}
+
+    /// 中得彩金
+    fn on_jackpot(&self, _pool: &Pool, _award: &JackpotAward) {}
+
+    /// 一次下注结束，`outcome` 已包含彩金判定结果，每次下注只触发一次
+    fn on_draw(&self, _pool: &Pool, _outcome: &DrawOutcome) {}
+
+    /// 即将执行结算，此时 Pool 尚未修改，结算失败时不会触发 `on_settlement`
+    fn on_settle(&self, _pool: &Pool, _kind: SettlementKind, _amount: u64) {}
+
+    /// 结算完成
+    fn on_settlement(&self, _pool: &Pool, _settlement: &Settlement) {}
+
+    /// 佣金比率与彩金比率已更新，参数为更新前的比率
+    fn on_ratios_updated(&self, _pool: &Pool, _brokerage_ratio: u64, _jackpot_ratio: u64) {}
+}
+
+/// Pool 上注册的观察者，克隆 Pool 时共享同一批观察者
+#[derive(Clone, Default)]
+pub(crate) struct Observers(Vec<Arc<dyn PoolObserver>>);
+
+impl Observers {
+    pub(crate) fn push(&mut self, observer: Arc<dyn PoolObserver>) {
+        self.0.push(observer);
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.0.clear();
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub(crate) fn iter(&self) -> impl Iterator<Item = &Arc<dyn PoolObserver>> {
+        self.0.iter()
+    }
+}
+
+impl fmt::Debug for Observers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Observers({})", self.0.len())
+    }
+}
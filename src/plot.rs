@@ -0,0 +1,205 @@
+use std::{error::Error, fmt, path::Path};
+
+use plotters::{coord::Shift, prelude::*};
+
+use crate::simulate::PoolHistory;
+
+/// 图表参数
+#[derive(Debug, Clone)]
+pub struct PlotOptions {
+    pub width: u32,    // 宽度，像素
+    pub height: u32,   // 高度，像素
+    pub title: String, // 标题
+}
+
+impl Default for PlotOptions {
+    fn default() -> Self {
+        Self {
+            width: 800,
+            height: 600,
+            title: String::from("Wave Chart"),
+        }
+    }
+}
+
+/// 绘图失败
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlotError(pub String);
+
+impl fmt::Display for PlotError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "plot failed: {}", self.0)
+    }
+}
+
+impl Error for PlotError {}
+
+fn plot_error<E: fmt::Display>(err: E) -> PlotError {
+    PlotError(err.to_string())
+}
+
+/// 将波浪绘制为折线图，路径以 `.svg` 结尾时输出 SVG，否则输出 PNG
+pub fn plot_wave<P: AsRef<Path>>(
+    wave: &[u64],
+    path: P,
+    options: &PlotOptions,
+) -> Result<(), PlotError> {
+    let path = path.as_ref();
+    let size = (options.width, options.height);
+    if is_svg(path) {
+        draw_wave(
+            SVGBackend::new(path, size).into_drawing_area(),
+            wave,
+            options,
+        )
+    } else {
+        draw_wave(
+            BitMapBackend::new(path, size).into_drawing_area(),
+            wave,
+            options,
+        )
+    }
+}
+
+/// 绘制模拟记录的池底轨迹、分段目标、派奖和分段变化，路径以 `.svg` 结尾时输出 SVG，否则输出 PNG
+pub fn plot_history<P: AsRef<Path>>(
+    history: &PoolHistory,
+    path: P,
+    options: &PlotOptions,
+) -> Result<(), PlotError> {
+    let path = path.as_ref();
+    let size = (options.width, options.height);
+    if is_svg(path) {
+        draw_history(
+            SVGBackend::new(path, size).into_drawing_area(),
+            history,
+            options,
+        )
+    } else {
+        draw_history(
+            BitMapBackend::new(path, size).into_drawing_area(),
+            history,
+            options,
+        )
+    }
+}
+
+fn is_svg(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("svg"))
+}
+
+fn draw_wave<DB: DrawingBackend>(
+    root: DrawingArea<DB, Shift>,
+    wave: &[u64],
+    options: &PlotOptions,
+) -> Result<(), PlotError>
+where
+    DB::ErrorType: 'static,
+{
+    root.fill(&WHITE).map_err(plot_error)?;
+    let y_max = wave.iter().max().cloned().unwrap_or(1).max(1);
+
+    let mut chart = ChartBuilder::on(&root)
+        .caption(&options.title, ("sans-serif", 40))
+        .margin(10)
+        .x_label_area_size(30)
+        .y_label_area_size(60)
+        .build_cartesian_2d(0..wave.len().max(1), 0..y_max)
+        .map_err(plot_error)?;
+    chart.configure_mesh().draw().map_err(plot_error)?;
+
+    chart
+        .draw_series(LineSeries::new(
+            wave.iter().enumerate().map(|(idx, &val)| (idx, val)),
+            BLUE,
+        ))
+        .map_err(plot_error)?
+        .label("Wave")
+        .legend(|(x, y)| PathElement::new([(x, y), (x + 20, y)], BLUE));
+
+    chart
+        .configure_series_labels()
+        .background_style(WHITE.mix(0.8))
+        .border_style(BLACK)
+        .draw()
+        .map_err(plot_error)?;
+    root.present().map_err(plot_error)
+}
+
+fn draw_history<DB: DrawingBackend>(
+    root: DrawingArea<DB, Shift>,
+    history: &PoolHistory,
+    options: &PlotOptions,
+) -> Result<(), PlotError>
+where
+    DB::ErrorType: 'static,
+{
+    root.fill(&WHITE).map_err(plot_error)?;
+    let points = &history.points;
+    let x_max = points.last().map_or(1, |p| p.round.max(1));
+    let y_max = points
+        .iter()
+        .map(|p| p.pot.max(p.segment.1))
+        .chain(std::iter::once(history.start))
+        .max()
+        .unwrap_or(1)
+        .max(1);
+
+    let mut chart = ChartBuilder::on(&root)
+        .caption(&options.title, ("sans-serif", 40))
+        .margin(10)
+        .x_label_area_size(30)
+        .y_label_area_size(60)
+        .build_cartesian_2d(0..x_max, 0..y_max)
+        .map_err(plot_error)?;
+    chart.configure_mesh().draw().map_err(plot_error)?;
+
+    chart
+        .draw_series(LineSeries::new(
+            std::iter::once((0, history.start)).chain(points.iter().map(|p| (p.round, p.pot))),
+            BLUE,
+        ))
+        .map_err(plot_error)?
+        .label("Pot")
+        .legend(|(x, y)| PathElement::new([(x, y), (x + 20, y)], BLUE));
+
+    chart
+        .draw_series(LineSeries::new(
+            points.iter().map(|p| (p.round, p.segment.1)),
+            RED.mix(0.6),
+        ))
+        .map_err(plot_error)?
+        .label("Target")
+        .legend(|(x, y)| PathElement::new([(x, y), (x + 20, y)], RED));
+
+    chart
+        .draw_series(
+            points
+                .iter()
+                .filter(|p| p.hit)
+                .map(|p| Circle::new((p.round, p.pot), 2, GREEN.filled())),
+        )
+        .map_err(plot_error)?
+        .label("Hit")
+        .legend(|(x, y)| Circle::new((x + 10, y), 3, GREEN.filled()));
+
+    chart
+        .draw_series(
+            history
+                .state_changes()
+                .map(|p| TriangleMarker::new((p.round, p.pot), 4, BLACK.filled())),
+        )
+        .map_err(plot_error)?
+        .label("Segment change")
+        .legend(|(x, y)| TriangleMarker::new((x + 10, y), 4, BLACK.filled()));
+
+    chart
+        .configure_series_labels()
+        .background_style(WHITE.mix(0.8))
+        .border_style(BLACK)
+        .draw()
+        .map_err(plot_error)?;
+    root.present().map_err(plot_error)
+}
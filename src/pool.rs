@@ -1,34 +1,104 @@
-use crate::wave;
-use rand::{rngs::StdRng, Rng, SeedableRng};
+use crate::{
+    config::{self, PoolConfig},
+    error::{PoolError, Result},
+    generators::WaveConfig,
+    jackpot::{self, JackpotAward, JackpotLink, JackpotRule, JackpotTier, SharedJackpot},
+    money::{Credits, Money},
+    observer::{Observers, PoolObserver},
+    rtp::{RtpController, RtpDivert, RtpTarget},
+    wave::{GoldenWave, WaveGenerator, WaveLeg, WaveStream},
+};
+use std::sync::Arc;
+
+use rand::{Rng, RngCore, SeedableRng};
+use rand_chacha::ChaCha12Rng;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
 
-const ASCENT_SPEED_RATE: u64 = 2000;
-const SPEED_RATE: u64 = 1000;
-const SPEED_BIG: u64 = 8000;
-const BIG_ODDS: u64 = 50;
 pub const RATIO: u64 = 10000; //比率 万分比
 
+/// 池子使用的随机数生成器，与 `StdRng` 同为 ChaCha12 算法，但状态可序列化
+pub type PoolRng = ChaCha12Rng;
+
 #[derive(Debug, Clone)]
 pub struct Pool {
-    pub id: u32,              // ID
-    pub owner_id: u32,        // 所有者 ID
-    pub bet_unit: u64,        // 每分价值
-    pub base_line: u64,       // 底线
-    pub boundary: u64,        // 边界线
-    pub brokerage_ratio: u64, // 佣金比率
-    pub jackpot_ratio: u64,   // 彩金比率
-    pub pot_ratio: u64,       // 池底比率
-    pub pot: u64,             // 当前池底
-    pub jackpot: u64,         // 彩金
-    pub suction: u64,         // 吸码量
-    pub brokerage: u64,       // 佣金
-    pub advance: u64,         // 垫分
-    waves: Vec<u64>,          // 波浪
-    segment: (u64, u64),      // 分段
-    rng: StdRng,
+    pub id: u32,                           // ID
+    pub owner_id: u32,                     // 所有者 ID
+    pub bet_unit: u64,                     // 每分价值
+    pub base_line: u64,                    // 底线
+    pub boundary: u64,                     // 边界线
+    pub brokerage_ratio: u64,              // 佣金比率
+    pub jackpot_ratio: u64,                // 彩金比率
+    pub pot_ratio: u64,                    // 池底比率
+    pub pot: u64,                          // 当前池底，万分比单位
+    pub jackpot: u64,                      // 彩金，万分比单位
+    pub suction: u64,                      // 吸码量，下注单位
+    pub brokerage: u64,                    // 佣金，万分比单位
+    pub advance: u64,                      // 垫分，万分比单位
+    pub ascent_speed_rate: u64,            // 上升时的减速比率
+    pub speed_rate: u64,                   // 下降时的加速比率
+    pub speed_big: u64,                    // 下降时大赔率的加速比率
+    pub big_odds: u64,                     // 大赔率的分界
+    pub jackpot_rule: Option<JackpotRule>, // 彩金规则
+    pub jackpot_tiers: Vec<JackpotTier>,   // 多级彩金
+    pub jackpot_link: Option<JackpotLink>, // 联动彩金，不包含在快照中
+    pub rtp: Option<RtpController>,        // 目标 RTP 控制
+    wave: Option<WaveConfig>,              // 波浪生成器配置，自定义生成器时为 None
+    generator: Arc<dyn WaveGenerator>,     // 波浪生成器
+    waves: WaveStream,                     // 波浪
+    waves_created: u64,                    // 生成过的波浪数，不包含在快照中
+    observers: Observers,                  // 观察者，不包含在快照中
+    segment: (u64, u64),                   // 分段
+    rng: PoolRng,
+}
+
+/// Pool 的完整快照，包含波浪、分段及随机数状态，可用于重启后恢复
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PoolSnapshot {
+    pub id: u32,
+    pub owner_id: u32,
+    pub bet_unit: u64,
+    pub base_line: u64,
+    pub boundary: u64,
+    pub brokerage_ratio: u64,
+    pub jackpot_ratio: u64,
+    pub pot_ratio: u64,
+    pub pot: u64,
+    pub jackpot: u64,
+    pub suction: u64,
+    pub brokerage: u64,
+    pub advance: u64,
+    #[serde(default = "config::default_ascent_speed_rate")]
+    pub ascent_speed_rate: u64,
+    #[serde(default = "config::default_speed_rate")]
+    pub speed_rate: u64,
+    #[serde(default = "config::default_speed_big")]
+    pub speed_big: u64,
+    #[serde(default = "config::default_big_odds")]
+    pub big_odds: u64,
+    #[serde(default)]
+    pub jackpot_rule: Option<JackpotRule>,
+    #[serde(default)]
+    pub jackpot_tiers: Vec<JackpotTier>,
+    #[serde(default)]
+    pub wave: Option<WaveConfig>,
+    #[serde(default)]
+    pub rtp: Option<RtpController>,
+    pub waves: Vec<u64>, // 已展开尚未消费的分段目标
+    #[serde(default)]
+    pub wave_legs: Vec<WaveLeg>, // 尚未展开的浪，栈序，末尾为下一段
+    #[serde(default)]
+    pub wave_rng: Option<PoolRng>, // 展开波浪使用的随机数，旧快照中没有尚未展开的浪
+    pub segment: (u64, u64),
+    pub rng: PoolRng,
 }
 
 impl Pool {
     /// 初始化一个新的 Pool 实例
+    ///
+    /// # Panics
+    ///
+    /// 参数不合法时 panic，需要处理错误时请使用 [`Pool::try_new`]
     pub fn new(
         id: u32,
         owner_id: u32,
@@ -38,24 +108,190 @@ impl Pool {
         boundary: u64,
         advance: u64,
     ) -> Self {
-        let pot = advance;
-        let boundary = boundary;
-        create_pool(
+        Self::try_new(
             id,
             owner_id,
             bet_unit,
-            0,
+            brokerage_ratio,
+            jackpot_ratio,
             boundary,
+            advance,
+        )
+        .expect("invalid pool config")
+    }
+
+    /// 初始化一个新的 Pool 实例，比率或边界不合法时返回错误
+    pub fn try_new(
+        id: u32,
+        owner_id: u32,
+        bet_unit: u64,
+        brokerage_ratio: u64,
+        jackpot_ratio: u64,
+        boundary: u64,
+        advance: u64,
+    ) -> Result<Self> {
+        let rng = PoolRng::from_entropy();
+        Self::with_rng(
+            id,
+            owner_id,
+            bet_unit,
             brokerage_ratio,
             jackpot_ratio,
-            pot,
-            0,
-            0,
-            0,
+            boundary,
+            advance,
+            rng,
+        )
+    }
+
+    /// 使用固定种子初始化 Pool，相同种子会得到完全相同的波浪与开奖序列
+    ///
+    /// # Panics
+    ///
+    /// 参数不合法时 panic，需要处理错误时请使用 [`Pool::try_with_seed`]
+    #[allow(clippy::too_many_arguments)]
+    pub fn with_seed(
+        id: u32,
+        owner_id: u32,
+        bet_unit: u64,
+        brokerage_ratio: u64,
+        jackpot_ratio: u64,
+        boundary: u64,
+        advance: u64,
+        seed: u64,
+    ) -> Self {
+        Self::try_with_seed(
+            id,
+            owner_id,
+            bet_unit,
+            brokerage_ratio,
+            jackpot_ratio,
+            boundary,
+            advance,
+            seed,
+        )
+        .expect("invalid pool config")
+    }
+
+    /// 使用固定种子初始化 Pool，比率或边界不合法时返回错误
+    #[allow(clippy::too_many_arguments)]
+    pub fn try_with_seed(
+        id: u32,
+        owner_id: u32,
+        bet_unit: u64,
+        brokerage_ratio: u64,
+        jackpot_ratio: u64,
+        boundary: u64,
+        advance: u64,
+        seed: u64,
+    ) -> Result<Self> {
+        let rng = PoolRng::seed_from_u64(seed);
+        Self::with_rng(
+            id,
+            owner_id,
+            bet_unit,
+            brokerage_ratio,
+            jackpot_ratio,
+            boundary,
+            advance,
+            rng,
+        )
+    }
+
+    /// 使用任意随机源初始化 Pool，内部随机数生成器的种子从该随机源中取得
+    ///
+    /// # Panics
+    ///
+    /// 参数不合法时 panic，需要处理错误时请使用 [`Pool::try_from_rng`]
+    #[allow(clippy::too_many_arguments)]
+    pub fn from_rng<R: RngCore>(
+        id: u32,
+        owner_id: u32,
+        bet_unit: u64,
+        brokerage_ratio: u64,
+        jackpot_ratio: u64,
+        boundary: u64,
+        advance: u64,
+        rng: R,
+    ) -> Self {
+        Self::try_from_rng(
+            id,
+            owner_id,
+            bet_unit,
+            brokerage_ratio,
+            jackpot_ratio,
+            boundary,
+            advance,
+            rng,
+        )
+        .expect("invalid pool config")
+    }
+
+    /// 使用任意随机源初始化 Pool，比率或边界不合法时返回错误
+    #[allow(clippy::too_many_arguments)]
+    pub fn try_from_rng<R: RngCore>(
+        id: u32,
+        owner_id: u32,
+        bet_unit: u64,
+        brokerage_ratio: u64,
+        jackpot_ratio: u64,
+        boundary: u64,
+        advance: u64,
+        mut rng: R,
+    ) -> Result<Self> {
+        let rng = PoolRng::from_seed(rng.gen());
+        Self::with_rng(
+            id,
+            owner_id,
+            bet_unit,
+            brokerage_ratio,
+            jackpot_ratio,
+            boundary,
             advance,
+            rng,
         )
     }
 
+    #[allow(clippy::too_many_arguments)]
+    fn with_rng(
+        id: u32,
+        owner_id: u32,
+        bet_unit: u64,
+        brokerage_ratio: u64,
+        jackpot_ratio: u64,
+        boundary: u64,
+        advance: u64,
+        rng: PoolRng,
+    ) -> Result<Self> {
+        let config = PoolConfig {
+            id,
+            owner_id,
+            bet_unit,
+            brokerage_ratio,
+            jackpot_ratio,
+            boundary,
+            advance,
+            ..Default::default()
+        };
+        create_pool(&config, advance, 0, 0, 0, rng)
+    }
+
+    /// 根据配置初始化一个新的 Pool 实例，池底从垫分开始
+    pub fn from_config(config: &PoolConfig) -> Result<Self> {
+        create_pool(config, config.advance, 0, 0, 0, PoolRng::from_entropy())
+    }
+
+    /// 根据配置和固定种子初始化一个新的 Pool 实例
+    pub fn from_config_with_seed(config: &PoolConfig, seed: u64) -> Result<Self> {
+        let rng = PoolRng::seed_from_u64(seed);
+        create_pool(config, config.advance, 0, 0, 0, rng)
+    }
+
+    /// 从持久化的数据加载 Pool，会重新生成波浪；需要完整恢复时请使用 [`Pool::restore`]
+    ///
+    /// # Panics
+    ///
+    /// 参数不合法时 panic，需要处理错误时请使用 [`Pool::try_load_pool`]
+    #[allow(clippy::too_many_arguments)]
     pub fn load_pool(
         id: u32,
         owner_id: u32,
@@ -70,7 +306,7 @@ impl Pool {
         brokerage: u64,
         advance: u64,
     ) -> Self {
-        create_pool(
+        Self::try_load_pool(
             id,
             owner_id,
             bet_unit,
@@ -84,107 +320,756 @@ impl Pool {
             brokerage,
             advance,
         )
+        .expect("invalid pool config")
     }
 
-    /// 根据传入的 WaveState 执行 draw 方法，并返回命中结果和 reward 值
-    pub fn draw(&mut self, bets: u64, odds: u64) -> (bool, u64) {
+    /// 从持久化的数据加载 Pool，比率或边界不合法时返回错误
+    #[allow(clippy::too_many_arguments)]
+    pub fn try_load_pool(
+        id: u32,
+        owner_id: u32,
+        bet_unit: u64,
+        base_line: u64,
+        boundary: u64,
+        brokerage_ratio: u64,
+        jackpot_ratio: u64,
+        pot: u64,
+        jackpot: u64,
+        suction: u64,
+        brokerage: u64,
+        advance: u64,
+    ) -> Result<Self> {
+        let config = PoolConfig {
+            id,
+            owner_id,
+            bet_unit,
+            brokerage_ratio,
+            jackpot_ratio,
+            base_line,
+            boundary,
+            advance,
+            ..Default::default()
+        };
+        create_pool(
+            &config,
+            pot,
+            jackpot,
+            suction,
+            brokerage,
+            PoolRng::from_entropy(),
+        )
+    }
+
+    /// 根据传入的 WaveState 执行 draw 方法，并返回本次下注的完整结果
+    ///
+    /// # Panics
+    ///
+    /// 赔率为 0 或金额溢出时 panic，需要处理错误时请使用 [`Pool::try_draw`]
+    pub fn draw(&mut self, bets: u64, odds: u64) -> DrawOutcome {
+        self.try_draw(bets, odds).expect("invalid draw")
+    }
+
+    /// 执行一次下注，赔率不合法、金额溢出或生成新波浪失败时返回错误且不修改池子状态，
+    /// 此时观察者可能已收到本局的 [`PoolObserver::on_bet`]
+    pub fn try_draw(&mut self, bets: u64, odds: u64) -> Result<DrawOutcome> {
+        let outcome = self.draw_round(bets, odds)?;
+        self.notify(|observer, pool| observer.on_draw(pool, &outcome));
+        Ok(outcome)
+    }
+
+    /// 执行一次下注，不判定彩金，也不通知 [`PoolObserver::on_draw`]
+    ///
+    /// 出错时恢复下注前的池底、佣金、彩金及随机数；波浪与分段只在新波浪生成成功后才替换，
+    /// 联动彩金在本局成功后才记入
+    fn draw_round(&mut self, bets: u64, odds: u64) -> Result<DrawOutcome> {
+        self.check_odds(odds)?;
+        let (raw_reward, reward) = self.calculate_reward(bets, odds)?;
         let state = self.get_state();
-        self.update_pool_with_bets(bets);
-        let (raw_reward, reward) = self.calculate_reward(bets, odds);
+        let pot_before = self.pot;
+        let segment_before = self.segment;
+        let waves_created = self.waves_created;
+        let checkpoint = self.checkpoint();
+        let (jackpot_contribution, linked) = self.update_pool_with_bets(bets)?;
+        self.notify(|observer, pool| observer.on_bet(pool, bets, odds));
 
-        let hit = match state {
-            WaveState::Ascent => self.ascent(odds, reward),
-            WaveState::Fall => self.fall(odds, reward),
+        let played = match state {
+            WaveState::Ascent => self.ascent(odds, reward).map(|hit| (hit, None)),
+            WaveState::Fall => self
+                .fall(odds, reward)
+                .map(|(hit, fall_state)| (hit, Some(fall_state))),
+        };
+        let (hit, fall_state) = match played {
+            Ok(played) => played,
+            Err(err) => {
+                self.rollback(checkpoint);
+                return Err(err);
+            }
         };
+        self.contribute_linked(linked);
+        if let Some(rtp) = &mut self.rtp {
+            rtp.record(bets, if hit { raw_reward } else { 0 }, self.pot_ratio);
+        }
 
+        let outcome = DrawOutcome {
+            hit,
+            bets,
+            odds,
+            raw_reward: if hit { raw_reward } else { 0 }, // 未命中时为 0
+            reward: if hit { reward } else { 0 },
+            state,
+            fall_state,
+            pot_before,
+            pot_after: self.pot,
+            segment_before,
+            segment_after: self.segment,
+            new_wave: self.waves_created != waves_created,
+            jackpot_contribution,
+            jackpot: None,
+            jackpot_drawn: false,
+        };
+        if outcome.fall_state == Some(FallState::Reflesh) {
+            self.notify(|observer, pool| observer.on_reflesh(pool, &outcome));
+        }
         if hit {
-            (true, raw_reward)
+            self.notify(|observer, pool| observer.on_hit(pool, &outcome));
         } else {
-            (false, 0) // 未命中时返回 0
+            self.notify(|observer, pool| observer.on_miss(pool, &outcome));
+        }
+        Ok(outcome)
+    }
+
+    /// 执行一次下注并判定彩金，彩金派彩记录在 [`DrawOutcome::jackpot`] 中
+    ///
+    /// # Panics
+    ///
+    /// 赔率为 0 或金额溢出时 panic，需要处理错误时请使用 [`Pool::try_draw_with_jackpot`]
+    pub fn draw_with_jackpot(&mut self, bets: u64, odds: u64) -> DrawOutcome {
+        self.try_draw_with_jackpot(bets, odds)
+            .expect("invalid draw")
+    }
+
+    /// 执行一次下注并判定彩金，赔率不合法或金额溢出时返回错误
+    pub fn try_draw_with_jackpot(&mut self, bets: u64, odds: u64) -> Result<DrawOutcome> {
+        let mut outcome = self.draw_round(bets, odds)?;
+        outcome.jackpot = self.draw_jackpot(bets);
+        outcome.jackpot_drawn = true;
+        if let Some(award) = &outcome.jackpot {
+            self.notify(|observer, pool| observer.on_jackpot(pool, award));
+        }
+        self.notify(|observer, pool| observer.on_draw(pool, &outcome));
+        Ok(outcome)
+    }
+
+    /// 检查一组下注依次执行时赔率是否合法、金额是否会溢出，不修改池子状态
+    ///
+    /// 按全部下注之和检查池底、佣金及彩金，命中派奖只会减少池底，因此逐个执行时也不会溢出；
+    /// 联动彩金由多个 Pool 共享，不在检查范围内
+    pub fn check_draws(&self, draws: &[(u64, u64)]) -> Result<()> {
+        let mut total: u64 = 0;
+        for &(bets, odds) in draws {
+            self.check_odds(odds)?;
+            self.calculate_reward(bets, odds)?;
+            total = total.checked_add(bets).ok_or(PoolError::Overflow)?;
+        }
+        let value = total
+            .checked_mul(self.bet_unit)
+            .ok_or(PoolError::Overflow)?;
+        // 目标 RTP 的分流比率逐局变化，按全部池底份额都可能分流检查
+        let divert = match &self.rtp {
+            Some(rtp) if rtp.target().divert != RtpDivert::None => self.pot_ratio,
+            _ => 0,
+        };
+        let fits = self.suction.checked_add(total).is_some()
+            && add_scaled(self.pot, self.pot_ratio, value).is_some()
+            && add_scaled(self.brokerage, self.brokerage_ratio, value).is_some()
+            && self
+                .split_jackpot(value)
+                .and_then(|(jackpot, _, _)| add_scaled(jackpot, divert, value))
+                .is_some();
+        match fits {
+            true => Ok(()),
+            false => Err(PoolError::Overflow),
+        }
+    }
+
+    /// 按彩金规则判定本次下注是否中得彩金，中奖时派发并重置彩金
+    ///
+    /// 依次判定联动彩金、多级彩金（从高到低）和 Pool::jackpot，每次下注最多中得一个彩金
+    pub fn draw_jackpot(&mut self, bets: u64) -> Option<JackpotAward> {
+        if let Some(link) = &self.jackpot_link {
+            let award =
+                link.jackpot
+                    .draw(self.owner_id, self.id, bets, self.bet_unit, &mut self.rng);
+            if award.is_some() {
+                return award;
+            }
+        }
+
+        let mut order: Vec<usize> = (0..self.jackpot_tiers.len()).collect();
+        order.sort_by_key(|&i| std::cmp::Reverse(self.jackpot_tiers[i].kind()));
+        for i in order {
+            if let Some(award) = self.jackpot_tiers[i].draw(bets, self.bet_unit, &mut self.rng) {
+                return Some(award);
+            }
+        }
+
+        let rule = self.jackpot_rule.as_ref()?;
+        if !rule.triggered(self.jackpot, bets, &mut self.rng) {
+            return None;
         }
+        let award = rule.award(self.jackpot, self.bet_unit);
+        self.jackpot = award.reseed;
+        Some(award)
     }
 
-    pub fn get_mut_rng(&mut self) -> &mut StdRng {
+    /// 连接联动彩金，彩金贡献中 `share` 份额（万分比）流入联动彩金
+    pub fn link_jackpot(&mut self, jackpot: SharedJackpot, share: u64) -> Result<()> {
+        jackpot
+            .snapshot()
+            .rule
+            .validate()
+            .map_err(|e| PoolError::InvalidConfig(e.to_string()))?;
+        let total = jackpot::tiers_share(&self.jackpot_tiers).saturating_add(share);
+        if total > RATIO {
+            return Err(PoolError::InvalidJackpotShare(total));
+        }
+        self.jackpot_link = Some(JackpotLink { jackpot, share });
+        Ok(())
+    }
+
+    /// 断开联动彩金，返回原来的连接
+    pub fn unlink_jackpot(&mut self) -> Option<JackpotLink> {
+        self.jackpot_link.take()
+    }
+
+    /// 池子的随机数生成器
+    ///
+    /// 返回类型由 `StdRng` 改为 [`PoolRng`]，依赖 `StdRng` 具体类型的调用方需要改用 `PoolRng`
+    /// 或 `impl Rng`
+    pub fn get_mut_rng(&mut self) -> &mut PoolRng {
         &mut self.rng
     }
 
+    /// 随机数生成器当前的位置，用于审计日志记录和回放每次下注前的随机数状态
+    pub fn rng_word_pos(&self) -> u128 {
+        self.rng.get_word_pos()
+    }
+
     /// 更新 brokerage_ratio jackpot_ratio 和 pot_ratio，确保它们之和等于 RATIO
+    ///
+    /// # Panics
+    ///
+    /// 比率之和超过 RATIO 时 panic，需要处理错误时请使用 [`Pool::try_update_ratios`]
     pub fn update_ratios(&mut self, new_brokerage_ratio: u64, new_jackpot_ratio: u64) {
+        self.try_update_ratios(new_brokerage_ratio, new_jackpot_ratio)
+            .expect("invalid ratios")
+    }
+
+    /// 更新 brokerage_ratio jackpot_ratio 和 pot_ratio，比率之和超过 RATIO 或分流的目标 RTP 高于池底比率时返回错误
+    pub fn try_update_ratios(
+        &mut self,
+        new_brokerage_ratio: u64,
+        new_jackpot_ratio: u64,
+    ) -> Result<()> {
+        let pot_ratio = pot_ratio(new_brokerage_ratio, new_jackpot_ratio)?;
+        if let Some(rtp) = &self.rtp {
+            if !rtp.target().fits_pot_ratio(pot_ratio) {
+                return Err(PoolError::InvalidConfig(format!(
+                    "target RTP {} exceeds pot ratio {}",
+                    rtp.target().target,
+                    pot_ratio
+                )));
+            }
+        }
+        let (brokerage_ratio, jackpot_ratio) = (self.brokerage_ratio, self.jackpot_ratio);
         self.brokerage_ratio = new_brokerage_ratio;
-        self.pot_ratio = RATIO - new_brokerage_ratio - new_jackpot_ratio;
+        self.jackpot_ratio = new_jackpot_ratio;
+        self.pot_ratio = pot_ratio;
+        self.notify(|observer, pool| {
+            observer.on_ratios_updated(pool, brokerage_ratio, jackpot_ratio)
+        });
+        Ok(())
+    }
+
+    /// 当前池底，万分比单位
+    pub fn pot_money(&self) -> Money {
+        Money(self.pot)
+    }
+
+    /// 当前池底，下注单位
+    pub fn pot_credits(&self) -> Credits {
+        Money(self.pot).to_credits(self.bet_unit)
+    }
+
+    /// 当前彩金，万分比单位
+    pub fn jackpot_money(&self) -> Money {
+        Money(self.jackpot)
+    }
+
+    /// 当前彩金，下注单位
+    pub fn jackpot_credits(&self) -> Credits {
+        Money(self.jackpot).to_credits(self.bet_unit)
+    }
+
+    /// 累计佣金，万分比单位
+    pub fn brokerage_money(&self) -> Money {
+        Money(self.brokerage)
+    }
+
+    /// 累计佣金，下注单位
+    pub fn brokerage_credits(&self) -> Credits {
+        Money(self.brokerage).to_credits(self.bet_unit)
+    }
+
+    /// 垫分，万分比单位
+    pub fn advance_money(&self) -> Money {
+        Money(self.advance)
+    }
+
+    /// 垫分，下注单位
+    pub fn advance_credits(&self) -> Credits {
+        Money(self.advance).to_credits(self.bet_unit)
+    }
+
+    /// 累计吸码量，下注单位
+    pub fn suction_credits(&self) -> Credits {
+        Credits(self.suction)
+    }
+
+    /// 累计吸码量，万分比单位，溢出时取最大值
+    pub fn suction_money(&self) -> Money {
+        Credits(self.suction)
+            .to_money(self.bet_unit)
+            .unwrap_or(Money(u64::MAX))
+    }
+
+    /// 当前池子对应的配置
+    pub fn config(&self) -> PoolConfig {
+        PoolConfig {
+            id: self.id,
+            owner_id: self.owner_id,
+            bet_unit: self.bet_unit,
+            brokerage_ratio: self.brokerage_ratio,
+            jackpot_ratio: self.jackpot_ratio,
+            base_line: self.base_line,
+            boundary: self.boundary,
+            advance: self.advance,
+            ascent_speed_rate: self.ascent_speed_rate,
+            speed_rate: self.speed_rate,
+            speed_big: self.speed_big,
+            big_odds: self.big_odds,
+            jackpot_rule: self.jackpot_rule.clone(),
+            jackpot_tiers: self
+                .jackpot_tiers
+                .iter()
+                .map(|tier| tier.config.clone())
+                .collect(),
+            wave: self.wave.clone().unwrap_or_default(),
+            rtp_target: self.rtp.as_ref().map(|rtp| rtp.target().clone()),
+        }
+    }
+
+    /// 更换波浪生成器，并立即从当前池底按新的生成器重新生成波浪
+    ///
+    /// 自定义生成器无法写入快照，恢复时需使用 [`Pool::restore_with_generator`]
+    pub fn set_wave_generator(&mut self, generator: Arc<dyn WaveGenerator>) -> Result<()> {
+        self.wave = None;
+        self.generator = generator;
+        self.reset_wave()
+    }
+
+    /// 按配置更换波浪生成器，并立即从当前池底重新生成波浪
+    pub fn set_wave_config(&mut self, wave: WaveConfig) -> Result<()> {
+        self.generator = wave.build()?;
+        self.wave = Some(wave);
+        self.reset_wave()
+    }
+
+    /// 注册观察者，观察者不包含在快照中，恢复后需重新注册
+    pub fn add_observer(&mut self, observer: Arc<dyn PoolObserver>) {
+        self.observers.push(observer);
+    }
+
+    pub fn clear_observers(&mut self) {
+        self.observers.clear();
+    }
+
+    pub fn observers_len(&self) -> usize {
+        self.observers.len()
+    }
+
+    /// 设置或取消目标 RTP，重新开始统计滑动窗口
+    pub fn set_rtp_target(&mut self, target: Option<RtpTarget>) -> Result<()> {
+        if let Some(target) = &target {
+            target
+                .validate()
+                .map_err(|e| PoolError::InvalidConfig(e.to_string()))?;
+            if !target.fits_pot_ratio(self.pot_ratio) {
+                return Err(PoolError::InvalidConfig(format!(
+                    "target RTP {} exceeds pot ratio {}",
+                    target.target, self.pot_ratio
+                )));
+            }
+        }
+        self.rtp = target.map(RtpController::new);
+        Ok(())
+    }
+
+    /// 波浪生成器配置，使用自定义生成器时为 None
+    pub fn wave_config(&self) -> Option<&WaveConfig> {
+        self.wave.as_ref()
+    }
+
+    pub fn wave_generator(&self) -> &Arc<dyn WaveGenerator> {
+        &self.generator
     }
 
     pub fn get_segment(&self) -> (u64, u64) {
         self.segment
     }
 
+    /// 剩余分段数的下限，波浪惰性展开，尚未细分的浪按一段计
     pub fn get_waves_len(&self) -> usize {
-        self.waves.len()
+        self.waves.len_hint()
+    }
+
+    /// 载入预先设计的波浪代替当前波浪，从当前池底开始依次走向各个目标，走完后再由生成器生成新的波浪
+    ///
+    /// 目标为空或超出底线与边界线时返回错误
+    pub fn load_wave(&mut self, targets: &[u64]) -> Result<()> {
+        if targets.is_empty() {
+            return Err(PoolError::InvalidWaveData(String::from("wave is empty")));
+        }
+        if let Some(target) = targets
+            .iter()
+            .find(|&&t| t < self.base_line || t > self.boundary)
+        {
+            return Err(PoolError::InvalidWaveData(format!(
+                "target {} outside base line {} and boundary {}",
+                target, self.base_line, self.boundary
+            )));
+        }
+        self.waves = WaveStream::from_targets(targets.iter().cloned());
+        let wave = self.waves.next(self.generator.as_ref()).unwrap_or(0);
+        self.create_segment(wave);
+        if self.waves.is_empty() {
+            self.create_wave()?;
+        }
+        Ok(())
+    }
+
+    /// 预读当前波浪中接下来最多 `n` 个分段目标，不影响开奖结果
+    pub fn peek_targets(&mut self, n: usize) -> Vec<u64> {
+        self.waves.peek(self.generator.as_ref(), n)
+    }
+
+    /// 生成当前状态的完整快照
+    pub fn snapshot(&self) -> PoolSnapshot {
+        PoolSnapshot {
+            id: self.id,
+            owner_id: self.owner_id,
+            bet_unit: self.bet_unit,
+            base_line: self.base_line,
+            boundary: self.boundary,
+            brokerage_ratio: self.brokerage_ratio,
+            jackpot_ratio: self.jackpot_ratio,
+            pot_ratio: self.pot_ratio,
+            pot: self.pot,
+            jackpot: self.jackpot,
+            suction: self.suction,
+            brokerage: self.brokerage,
+            advance: self.advance,
+            ascent_speed_rate: self.ascent_speed_rate,
+            speed_rate: self.speed_rate,
+            speed_big: self.speed_big,
+            big_odds: self.big_odds,
+            jackpot_rule: self.jackpot_rule.clone(),
+            jackpot_tiers: self.jackpot_tiers.clone(),
+            wave: self.wave.clone(),
+            rtp: self.rtp.clone(),
+            waves: self.waves.targets().iter().cloned().collect(),
+            wave_legs: self.waves.legs().to_vec(),
+            wave_rng: Some(self.waves.rng().clone()),
+            segment: self.segment,
+            rng: self.rng.clone(),
+        }
+    }
+
+    /// 从快照恢复 Pool，波浪、分段和随机数状态与快照时完全一致
+    ///
+    /// 联动彩金由多个 Pool 共享，不在快照中，恢复后需重新调用 [`Pool::link_jackpot`]；
+    /// 波浪生成器按快照中的配置重建，快照中没有配置时使用默认的 [`GoldenWave`]，
+    /// 自定义生成器请使用 [`Pool::restore_with_generator`]。快照中的配置不合法时返回错误
+    pub fn restore(snapshot: PoolSnapshot) -> Result<Self> {
+        let generator = match &snapshot.wave {
+            Some(wave) => wave.build()?,
+            None => Arc::new(GoldenWave::default()),
+        };
+        Self::restore_with_generator(snapshot, generator)
+    }
+
+    /// 从快照恢复 Pool，并使用指定的波浪生成器生成之后的波浪，快照中的配置不合法时返回错误
+    pub fn restore_with_generator(
+        snapshot: PoolSnapshot,
+        generator: Arc<dyn WaveGenerator>,
+    ) -> Result<Self> {
+        let pool = Pool {
+            id: snapshot.id,
+            owner_id: snapshot.owner_id,
+            bet_unit: snapshot.bet_unit,
+            base_line: snapshot.base_line,
+            boundary: snapshot.boundary,
+            brokerage_ratio: snapshot.brokerage_ratio,
+            jackpot_ratio: snapshot.jackpot_ratio,
+            pot_ratio: snapshot.pot_ratio,
+            pot: snapshot.pot,
+            jackpot: snapshot.jackpot,
+            suction: snapshot.suction,
+            brokerage: snapshot.brokerage,
+            advance: snapshot.advance,
+            ascent_speed_rate: snapshot.ascent_speed_rate,
+            speed_rate: snapshot.speed_rate,
+            speed_big: snapshot.speed_big,
+            big_odds: snapshot.big_odds,
+            jackpot_rule: snapshot.jackpot_rule,
+            jackpot_tiers: snapshot.jackpot_tiers,
+            jackpot_link: None,
+            rtp: snapshot.rtp,
+            wave: snapshot.wave,
+            generator,
+            waves: match snapshot.wave_rng {
+                Some(rng) => WaveStream::from_parts(snapshot.waves, snapshot.wave_legs, rng),
+                None => WaveStream::from_targets(snapshot.waves),
+            },
+            segment: snapshot.segment,
+            waves_created: 0,
+            observers: Observers::default(),
+            rng: snapshot.rng,
+        };
+        pool.config().check()?;
+        if pool.pot_ratio != pot_ratio(pool.brokerage_ratio, pool.jackpot_ratio)? {
+            return Err(PoolError::InvalidConfig(format!(
+                "pot ratio {} does not match brokerage ratio {} and jackpot ratio {}",
+                pool.pot_ratio, pool.brokerage_ratio, pool.jackpot_ratio
+            )));
+        }
+        Ok(pool)
+    }
+}
+
+impl TryFrom<PoolSnapshot> for Pool {
+    type Error = PoolError;
+
+    fn try_from(snapshot: PoolSnapshot) -> Result<Self> {
+        Pool::restore(snapshot)
     }
 }
 
 fn create_pool(
-    id: u32,
-    owner_id: u32,
-    bet_unit: u64,
-    base_line: u64,
-    boundary: u64,
-    brokerage_ratio: u64,
-    jackpot_ratio: u64,
+    config: &PoolConfig,
     pot: u64,
     jackpot: u64,
     suction: u64,
     brokerage: u64,
-    advance: u64,
-) -> Pool {
-    let mut rng = StdRng::from_entropy();
-    let mut waves = wave::create_wave(pot, 0, boundary, &mut rng);
-    let segment = wave::create_segment(&mut waves, pot);
-
-    Pool {
-        id,
-        owner_id,
-        bet_unit,
-        brokerage_ratio,
-        jackpot_ratio,
-        base_line,
-        boundary,
-        pot_ratio: RATIO - brokerage_ratio - jackpot_ratio,
+    mut rng: PoolRng,
+) -> Result<Pool> {
+    config.check()?;
+    let jackpot = match &config.jackpot_rule {
+        Some(rule) => jackpot.max(rule.seed),
+        None => jackpot,
+    };
+    let generator = config.wave.build()?;
+    let mut waves = WaveStream::generate(
+        generator.as_ref(),
+        pot,
+        config.base_line,
+        config.boundary,
+        &mut rng,
+    )?;
+    let segment = (pot, waves.next(generator.as_ref()).unwrap_or(0));
+
+    Ok(Pool {
+        id: config.id,
+        owner_id: config.owner_id,
+        bet_unit: config.bet_unit,
+        brokerage_ratio: config.brokerage_ratio,
+        jackpot_ratio: config.jackpot_ratio,
+        base_line: config.base_line,
+        boundary: config.boundary,
+        pot_ratio: config.pot_ratio(),
         pot,
         jackpot,
         suction,
         brokerage,
-        advance,
+        advance: config.advance,
+        ascent_speed_rate: config.ascent_speed_rate,
+        speed_rate: config.speed_rate,
+        speed_big: config.speed_big,
+        big_odds: config.big_odds,
+        jackpot_rule: config.jackpot_rule.clone(),
+        jackpot_tiers: config
+            .jackpot_tiers
+            .iter()
+            .cloned()
+            .map(JackpotTier::new)
+            .collect(),
+        jackpot_link: None,
+        rtp: config.rtp_target.clone().map(RtpController::new),
+        wave: Some(config.wave.clone()),
+        generator,
         waves,
         segment,
+        waves_created: 1,
+        observers: Observers::default(),
         rng,
-    }
+    })
+}
+
+/// 根据佣金比率与彩金比率计算池底比率
+fn pot_ratio(brokerage_ratio: u64, jackpot_ratio: u64) -> Result<u64> {
+    brokerage_ratio
+        .checked_add(jackpot_ratio)
+        .and_then(|used| RATIO.checked_sub(used))
+        .ok_or(PoolError::InvalidRatios {
+            brokerage_ratio,
+            jackpot_ratio,
+        })
 }
 
 impl Pool {
-    /// 更新池底金额及相关属性
-    fn update_pool_with_bets(&mut self, bet: u64) {
-        self.suction += bet;
-        self.pot += self.pot_ratio * bet;
-        self.brokerage += self.brokerage_ratio * bet;
-        self.jackpot += self.jackpot_ratio * bet;
+    /// 检查赔率是否合法，放大到万分比后不会溢出
+    ///
+    /// 下降时的加速比率必须小于 RATIO，否则随机范围为空；从快照恢复的 Pool 未必经过配置校验，
+    /// 因此在修改池子状态前一并检查
+    fn check_odds(&self, odds: u64) -> Result<()> {
+        if odds == 0 {
+            return Err(PoolError::InvalidOdds(odds));
+        }
+        let speed = self.fall_speed(odds);
+        if speed >= RATIO {
+            return Err(PoolError::InvalidConfig(format!(
+                "fall speed rate {} must be less than RATIO",
+                speed
+            )));
+        }
+        self.ascent_speed_rate
+            .checked_add(RATIO)
+            .and_then(|rate| odds.checked_mul(rate))
+            .map(|_| ())
+            .ok_or(PoolError::Overflow)
     }
 
-    /// 计算当前下注及赔率的奖励
-    fn calculate_reward(&self, bets: u64, odds: u64) -> (u64, u64) {
-        let raw_reward = bets * odds;
-        let reward = raw_reward * RATIO;
-        (raw_reward, reward)
+    /// 更新池底金额及相关属性，任一项溢出时不做任何修改
+    ///
+    /// 下注按每分价值换算后再按比率分配，结果为万分比单位；目标 RTP 选择分流时，
+    /// 池底份额中的分流部分计入 Pool::jackpot
+    ///
+    /// 返回流入各类彩金的金额及其中流入联动彩金的部分，联动彩金只做检查，由调用方在本局成功后记入
+    fn update_pool_with_bets(&mut self, bet: u64) -> Result<(u64, u64)> {
+        let value = bet.checked_mul(self.bet_unit).ok_or(PoolError::Overflow)?;
+        let divert = self
+            .rtp
+            .as_ref()
+            .map_or(0, |rtp| rtp.divert_ratio(self.pot_ratio));
+        let diverted = divert.checked_mul(value).ok_or(PoolError::Overflow)?;
+        let suction = self.suction.checked_add(bet);
+        let pot = add_scaled(self.pot, self.pot_ratio - divert, value);
+        let brokerage = add_scaled(self.brokerage, self.brokerage_ratio, value);
+        let jackpot = self
+            .split_jackpot(value)
+            .and_then(|(jackpot, tiers, linked)| {
+                Some((jackpot.checked_add(diverted)?, tiers, linked))
+            });
+        match (suction, pot, brokerage, jackpot) {
+            (Some(suction), Some(pot), Some(brokerage), Some((jackpot, tiers, linked))) => {
+                if let Some(link) = &self.jackpot_link {
+                    if !link.jackpot.can_contribute(self.owner_id, self.id, linked) {
+                        return Err(PoolError::Overflow);
+                    }
+                }
+                self.suction = suction;
+                self.pot = pot;
+                self.brokerage = brokerage;
+                self.jackpot = jackpot;
+                for (tier, value) in self.jackpot_tiers.iter_mut().zip(tiers) {
+                    tier.value = value;
+                }
+                Ok((self.jackpot_ratio * value + diverted, linked))
+            }
+            _ => Err(PoolError::Overflow),
+        }
+    }
+
+    /// 记入联动彩金；检查之后其他 Pool 的贡献导致溢出时，这部分留在 Pool::jackpot
+    fn contribute_linked(&mut self, linked: u64) {
+        let Some(link) = &self.jackpot_link else {
+            return;
+        };
+        if linked > 0
+            && link
+                .jackpot
+                .contribute(self.owner_id, self.id, linked)
+                .is_none()
+        {
+            self.jackpot = self.jackpot.saturating_add(linked);
+        }
+    }
+
+    /// 下注前可能被本局修改的池子状态
+    fn checkpoint(&self) -> RoundCheckpoint {
+        RoundCheckpoint {
+            suction: self.suction,
+            pot: self.pot,
+            brokerage: self.brokerage,
+            jackpot: self.jackpot,
+            tiers: self.jackpot_tiers.iter().map(|tier| tier.value).collect(),
+            word_pos: self.rng.get_word_pos(),
+        }
+    }
+
+    fn rollback(&mut self, checkpoint: RoundCheckpoint) {
+        self.suction = checkpoint.suction;
+        self.pot = checkpoint.pot;
+        self.brokerage = checkpoint.brokerage;
+        self.jackpot = checkpoint.jackpot;
+        for (tier, value) in self.jackpot_tiers.iter_mut().zip(checkpoint.tiers) {
+            tier.value = value;
+        }
+        self.rng.set_word_pos(checkpoint.word_pos);
+        if let Some(rtp) = &mut self.rtp {
+            rtp.discard_round();
+        }
+    }
+
+    /// 计算彩金贡献的分配，返回 Pool::jackpot 新值、各级彩金新值与流入联动彩金的部分
+    fn split_jackpot(&self, value: u64) -> Option<(u64, Vec<u64>, u64)> {
+        let contribution = self.jackpot_ratio.checked_mul(value)?;
+        let linked = match &self.jackpot_link {
+            Some(link) => contribution.checked_mul(link.share)? / RATIO,
+            None => 0,
+        };
+        let (tiers, rest) = jackpot::split_contribution(&self.jackpot_tiers, contribution)?;
+        let jackpot = self.jackpot.checked_add(rest.checked_sub(linked)?)?;
+        Some((jackpot, tiers, linked))
+    }
+
+    /// 计算当前下注及赔率的奖励，返回下注单位与万分比单位的奖励
+    fn calculate_reward(&self, bets: u64, odds: u64) -> Result<(u64, u64)> {
+        let raw_reward = bets.checked_mul(odds).ok_or(PoolError::Overflow)?;
+        let reward = Credits(raw_reward)
+            .to_money(self.bet_unit)
+            .ok_or(PoolError::Overflow)?;
+        Ok((raw_reward, reward.0))
     }
 
     /// 上升逻辑处理，根据状态决定是否减少池底或调整波浪，返回是否命中
-    fn ascent(&mut self, odds: u64, reward: u64) -> bool {
+    fn ascent(&mut self, odds: u64, reward: u64) -> Result<bool> {
         if self.analyzing_ascent(reward) && self.ascent_run(odds) {
             self.decrease_pot(reward);
-            true
+            Ok(true)
         } else {
-            self.ascent_action();
-            false
+            self.ascent_action()?;
+            Ok(false)
         }
     }
 
@@ -202,40 +1087,44 @@ impl Pool {
 
     /// 上升时执行的奖励计算及判定
     fn ascent_run(&mut self, odds: u64) -> bool {
-        let new_odds = odds * (ASCENT_SPEED_RATE + RATIO); // 计算并放大到万分比表示
+        let new_odds = odds * (self.ascent_speed_rate + RATIO); // 计算并放大到万分比表示
+        let new_odds = self.rtp_odds(new_odds, WaveState::Ascent);
         self.run(new_odds)
     }
 
     /// 当上升条件未达到时，执行波浪调整
-    fn ascent_action(&mut self) {
+    fn ascent_action(&mut self) -> Result<()> {
         let (_, destination) = self.segment;
         let pot = self.pot;
         if pot > destination {
-            self.consume_and_segment();
+            self.consume_and_segment()?;
         }
+        Ok(())
     }
 
-    /// 下降逻辑处理，根据状态决定是否减少池底或调整波浪，返回是否命中
-    fn fall(&mut self, odds: u64, reward: u64) -> bool {
-        match self.analyzing_fall(reward) {
+    /// 下降逻辑处理，根据状态决定是否减少池底或调整波浪，返回是否命中及所走的分支
+    fn fall(&mut self, odds: u64, reward: u64) -> Result<(bool, FallState)> {
+        let fall_state = self.analyzing_fall(reward);
+        let hit = match fall_state {
             FallState::Normal => {
                 if self.fall_run(odds) {
-                    self.fall_action(reward);
+                    self.fall_action(reward)?;
                     true
                 } else {
                     false
                 }
             }
             FallState::Win => {
-                self.fall_action(reward);
+                self.fall_action(reward)?;
                 true
             }
             FallState::Reflesh => {
-                self.consume_and_segment();
+                self.consume_and_segment()?;
                 // self.create_new_wave_and_segment();
                 false
             }
-        }
+        };
+        Ok((hit, fall_state))
     }
 
     fn analyzing_fall(&self, reward: u64) -> FallState {
@@ -255,21 +1144,38 @@ impl Pool {
 
     /// 下降时执行的奖励计算及判定
     fn fall_run(&mut self, odds: u64) -> bool {
-        let new_odds = if odds >= BIG_ODDS {
-            odds * (RATIO - SPEED_BIG)
-        } else {
-            odds * (RATIO - SPEED_RATE)
-        };
+        // check_odds 已保证加速比率小于 RATIO 且放大后不会溢出
+        let new_odds = odds * RATIO.saturating_sub(self.fall_speed(odds));
+        let new_odds = self.rtp_odds(new_odds, WaveState::Fall);
         self.run(new_odds)
     }
 
+    /// 启用目标 RTP 时按控制器的调整缩放随机范围
+    fn rtp_odds(&mut self, odds: u64, state: WaveState) -> u64 {
+        let (pot, boundary) = (self.pot, self.boundary);
+        match &mut self.rtp {
+            Some(rtp) => rtp.adjust_odds(odds, state, pot >= boundary),
+            None => odds,
+        }
+    }
+
+    /// 下降时按赔率选择的加速比率
+    fn fall_speed(&self, odds: u64) -> u64 {
+        if odds >= self.big_odds {
+            self.speed_big
+        } else {
+            self.speed_rate
+        }
+    }
+
     /// 执行下降操作，更新池底及波浪
-    fn fall_action(&mut self, reward: u64) {
+    fn fall_action(&mut self, reward: u64) -> Result<()> {
         self.decrease_pot(reward);
         let (_, destination) = self.segment;
         if self.pot <= destination {
-            self.consume_and_segment();
+            self.consume_and_segment()?;
         }
+        Ok(())
     }
 
     /// 生成随机数判断胜负
@@ -290,16 +1196,23 @@ impl Pool {
     }
 
     /// 从波浪中获取第一个元素并创建分段，如果波浪为空则创建新波浪
-    fn consume_and_segment(&mut self) {
-        if let Some(wave) = self.waves.first().cloned() {
-            self.waves.remove(0); // 删除第一个元素
+    ///
+    /// 新波浪生成失败时波浪与分段保持不变
+    fn consume_and_segment(&mut self) -> Result<()> {
+        let mut waves = self.waves.clone();
+        if let Some(wave) = waves.next(self.generator.as_ref()) {
+            // 如果 waves 已空，则先创建新的波浪
+            let next = match waves.is_empty() {
+                true => Some(self.generate_wave()?),
+                false => None,
+            };
+            self.waves = waves;
             self.create_segment(wave);
-
-            // 如果 waves 已空，则创建新的波浪
-            if self.waves.is_empty() {
-                self.create_wave();
+            if let Some(next) = next {
+                self.install_wave(next);
             }
         }
+        Ok(())
     }
 
     // /// 生成新的波浪及分段
@@ -311,16 +1224,55 @@ impl Pool {
     /// 创建新的分段
     fn create_segment(&mut self, wave: u64) {
         let segment = (self.pot, wave);
-        self.segment = segment;
+        let before = std::mem::replace(&mut self.segment, segment);
+        if before != segment {
+            self.notify(|observer, pool| observer.on_segment_change(pool, before, segment));
+        }
+    }
+
+    /// 从当前池底重新生成波浪及分段，用于池底被结算等外部操作改变之后
+    pub(crate) fn reset_wave(&mut self) -> Result<()> {
+        self.create_wave()?;
+        let wave = self.waves.next(self.generator.as_ref()).unwrap_or(0);
+        self.create_segment(wave);
+        if self.waves.is_empty() {
+            self.create_wave()?;
+        }
+        Ok(())
     }
 
     /// 创建新的波浪
-    fn create_wave(&mut self) {
-        let waves = wave::create_wave(self.pot, self.base_line, self.boundary, self.get_mut_rng());
+    fn create_wave(&mut self) -> Result<()> {
+        let waves = self.generate_wave()?;
+        self.install_wave(waves);
+        Ok(())
+    }
+
+    /// 从当前池底生成波浪，不修改波浪及分段
+    fn generate_wave(&mut self) -> Result<WaveStream> {
+        WaveStream::generate(
+            self.generator.as_ref(),
+            self.pot,
+            self.base_line,
+            self.boundary,
+            &mut self.rng,
+        )
+    }
+
+    fn install_wave(&mut self, waves: WaveStream) {
         self.waves = waves;
+        self.waves_created = self.waves_created.wrapping_add(1);
+        self.notify(|observer, pool| observer.on_new_wave(pool));
     }
 
-    fn get_state(&self) -> WaveState {
+    pub(crate) fn notify<F: Fn(&dyn PoolObserver, &Pool)>(&self, f: F) {
+        for observer in self.observers.iter() {
+            f(observer.as_ref(), self);
+        }
+    }
+
+    /// 当前池底相对分段目标的走势
+    pub fn get_state(&self) -> WaveState {
         let (_, destination) = self.segment;
         if self.pot > destination {
             WaveState::Fall
@@ -330,13 +1282,51 @@ impl Pool {
     }
 }
 
-#[derive(Debug)]
+/// 一局下注开始前的状态，出错时用于恢复
+struct RoundCheckpoint {
+    suction: u64,
+    pot: u64,
+    brokerage: u64,
+    jackpot: u64,
+    tiers: Vec<u64>, // 各级彩金
+    word_pos: u128,  // 随机数位置
+}
+
+/// 计算 `value + ratio * bet`，溢出时返回 None
+fn add_scaled(value: u64, ratio: u64, bet: u64) -> Option<u64> {
+    ratio
+        .checked_mul(bet)
+        .and_then(|scaled| value.checked_add(scaled))
+}
+
+/// 单次下注的完整结果，用于审计及排查异常的连中或连空
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DrawOutcome {
+    pub hit: bool,                     // 是否命中
+    pub bets: u64,                     // 下注额，下注单位
+    pub odds: u64,                     // 赔率
+    pub raw_reward: u64,               // 派奖，下注单位，未命中为 0
+    pub reward: u64,                   // 派奖，万分比单位，未命中为 0
+    pub state: WaveState,              // 下注时的走势
+    pub fall_state: Option<FallState>, // 下降时所走的分支，上升时为 None
+    pub pot_before: u64,               // 下注前池底
+    pub pot_after: u64,                // 下注后池底
+    pub segment_before: (u64, u64),    // 下注前分段
+    pub segment_after: (u64, u64),     // 下注后分段
+    pub new_wave: bool,                // 本次下注是否生成了新的波浪
+    pub jackpot_contribution: u64,     // 流入各类彩金的金额，万分比单位
+    pub jackpot: Option<JackpotAward>, // 彩金派彩，仅 draw_with_jackpot 判定
+    #[serde(default)]
+    pub jackpot_drawn: bool, // 是否判定了彩金，即通过 draw_with_jackpot 下注
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum WaveState {
     Ascent,
     Fall,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum FallState {
     Normal,
     Win,
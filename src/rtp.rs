@@ -0,0 +1,257 @@
+use std::collections::VecDeque;
+
+use serde::{Deserialize, Serialize};
+use validator::{Validate, ValidationError};
+
+use crate::pool::{WaveState, RATIO};
+
+/// 滑动窗口划分的桶数，窗口按桶整体滑动
+pub const RTP_BUCKETS: u64 = 20;
+
+pub const DEFAULT_RTP_TOLERANCE: u64 = 100;
+pub const DEFAULT_RTP_WINDOW: u64 = 10000;
+pub const DEFAULT_RTP_STEP: u64 = 1;
+pub const DEFAULT_RTP_MAX_ADJUST: u64 = 500;
+
+/// 目标 RTP 的调节方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RtpDivert {
+    #[default]
+    None, // 调整 ascent_run / fall_run 的命中概率，不分流
+    Jackpot, // 每次下注中 `pot_ratio - target` 的部分不进入池底而计入 Pool::jackpot
+}
+
+/// 目标 RTP 配置
+///
+/// 默认按滑动窗口内的实际 RTP 调整命中概率，池底受波浪约束，长期 RTP 仍趋向池底比率；
+/// 需要长期 RTP 低于池底比率时选择 [`RtpDivert::Jackpot`]，把池底份额中超出目标的部分分流到彩金，
+/// 此时目标不能高于池底比率
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Validate)]
+#[validate(schema(function = "validate_rtp_target"))]
+pub struct RtpTarget {
+    #[validate(range(min = 1, max = RATIO, message = "Target RTP must be within 1..=RATIO"))]
+    pub target: u64, // 目标 RTP，万分比
+    #[serde(default = "default_tolerance")]
+    pub tolerance: u64, // 允许偏差，万分比
+    #[serde(default = "default_window")]
+    #[validate(range(
+        min = RTP_BUCKETS,
+        message = "Window must cover at least RTP_BUCKETS rounds"
+    ))]
+    pub window: u64, // 滑动窗口的局数
+    #[serde(default = "default_step")]
+    #[validate(range(min = 1, message = "Step must be positive"))]
+    pub step: u64, // 超出范围时每局的调整量，万分比
+    #[serde(default = "default_max_adjust")]
+    #[validate(range(
+        exclusive_max = RATIO,
+        message = "Max adjust must be less than RATIO"
+    ))]
+    pub max_adjust: u64, // 调整量的上限，万分比
+    #[serde(default)]
+    pub divert: RtpDivert, // 调节方式
+}
+
+impl RtpTarget {
+    pub fn new(target: u64) -> Self {
+        Self {
+            target,
+            tolerance: DEFAULT_RTP_TOLERANCE,
+            window: DEFAULT_RTP_WINDOW,
+            step: DEFAULT_RTP_STEP,
+            max_adjust: DEFAULT_RTP_MAX_ADJUST,
+            divert: RtpDivert::default(),
+        }
+    }
+
+    /// 目标能否在该池底比率下达到，分流只能降低 RTP
+    pub fn fits_pot_ratio(&self, pot_ratio: u64) -> bool {
+        self.divert == RtpDivert::None || self.target <= pot_ratio
+    }
+}
+
+fn validate_rtp_target(target: &RtpTarget) -> Result<(), ValidationError> {
+    if target.tolerance > target.target {
+        let mut err = ValidationError::new("tolerance");
+        err.message = Some("Tolerance must not exceed target RTP".into());
+        return Err(err);
+    }
+    Ok(())
+}
+
+fn default_tolerance() -> u64 {
+    DEFAULT_RTP_TOLERANCE
+}
+
+fn default_window() -> u64 {
+    DEFAULT_RTP_WINDOW
+}
+
+fn default_step() -> u64 {
+    DEFAULT_RTP_STEP
+}
+
+fn default_max_adjust() -> u64 {
+    DEFAULT_RTP_MAX_ADJUST
+}
+
+/// 滑动窗口中的一个桶，下注与派奖均为下注单位
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+struct RtpBucket {
+    rounds: u64,
+    bet: u64,
+    paid: u64,
+}
+
+/// 目标 RTP 控制器，按滑动窗口内的实际 RTP 调整命中概率或分流比率
+///
+/// 调整只在实际 RTP 超出允许范围时累积，回到范围内后逐局回落，且限制在 `max_adjust` 以内；
+/// 调整因逆着走势而未生效、或分流比率已到 0 或池底比率时，不再朝同一方向累积，
+/// 避免积分饱和
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RtpController {
+    target: RtpTarget,            // 目标配置
+    buckets: VecDeque<RtpBucket>, // 滑动窗口，末尾为最新的桶
+    bet: u64,                     // 窗口内的下注额
+    paid: u64,                    // 窗口内的派奖额
+    #[serde(default)]
+    bias: i64, // 调整量，万分比，正数提高 RTP
+    #[serde(skip)]
+    limited: bool, // 本局的调整是否因走势未生效
+}
+
+impl RtpController {
+    pub fn new(target: RtpTarget) -> Self {
+        Self {
+            target,
+            buckets: VecDeque::new(),
+            bet: 0,
+            paid: 0,
+            bias: 0,
+            limited: false,
+        }
+    }
+
+    pub fn target(&self) -> &RtpTarget {
+        &self.target
+    }
+
+    /// 窗口内的实际 RTP，万分比，没有下注时为 None
+    pub fn rtp(&self) -> Option<u64> {
+        if self.bet == 0 {
+            return None;
+        }
+        Some((self.paid as u128 * RATIO as u128 / self.bet as u128).min(u64::MAX as u128) as u64)
+    }
+
+    /// 当前调整量，万分比，正数提高 RTP
+    pub fn bias(&self) -> i64 {
+        self.bias
+    }
+
+    /// 窗口内的局数
+    pub fn rounds(&self) -> u64 {
+        self.buckets.iter().map(|bucket| bucket.rounds).sum()
+    }
+
+    /// 实际 RTP 是否在允许范围内，窗口未满一个桶时视为在范围内
+    pub fn in_band(&self) -> bool {
+        match self.rtp() {
+            Some(rtp) if self.rounds() >= self.bucket_size() => {
+                rtp.abs_diff(self.target.target) <= self.target.tolerance
+            }
+            _ => true,
+        }
+    }
+
+    /// 按调整缩放 `Pool::run` 的随机范围，命中概率与 `RATIO + bias` 成正比
+    ///
+    /// 只在顺着当前走势时生效：上升时只降低命中概率，且池底到达边界线后不再降低，
+    /// 下降时只提高命中概率，池底仍按波浪在原来的范围内波动
+    pub fn adjust_odds(&mut self, odds: u64, state: WaveState, above_boundary: bool) -> u64 {
+        if self.target.divert != RtpDivert::None {
+            return odds;
+        }
+        let bias = match (state, self.bias) {
+            (WaveState::Ascent, bias) if bias > 0 || above_boundary => 0,
+            (WaveState::Fall, bias) if bias < 0 => 0,
+            (_, bias) => bias,
+        };
+        self.limited |= bias != self.bias;
+        let scale = (RATIO as i64 + bias) as u128;
+        let odds = odds as u128 * RATIO as u128 / scale;
+        odds.clamp(1, u64::MAX as u128) as u64
+    }
+
+    /// 放弃本局记录的走势限制，用于出错回滚的下注
+    pub(crate) fn discard_round(&mut self) {
+        self.limited = false;
+    }
+
+    /// 每次下注中不进入池底而计入彩金的比率，万分比，不超过池底比率；不分流时为 0
+    pub fn divert_ratio(&self, pot_ratio: u64) -> u64 {
+        if self.target.divert == RtpDivert::None {
+            return 0;
+        }
+        let base = pot_ratio.saturating_sub(self.target.target) as i64;
+        (base - self.bias).clamp(0, pot_ratio as i64) as u64
+    }
+
+    /// 记录一局的下注与派奖，并按窗口内的实际 RTP 调整
+    pub fn record(&mut self, bet: u64, paid: u64, pot_ratio: u64) {
+        let bucket_size = self.bucket_size();
+        if self
+            .buckets
+            .back()
+            .is_none_or(|bucket| bucket.rounds >= bucket_size)
+        {
+            self.buckets.push_back(RtpBucket::default());
+            if self.buckets.len() as u64 > RTP_BUCKETS {
+                if let Some(expired) = self.buckets.pop_front() {
+                    self.bet = self.bet.saturating_sub(expired.bet);
+                    self.paid = self.paid.saturating_sub(expired.paid);
+                }
+            }
+        }
+        if let Some(bucket) = self.buckets.back_mut() {
+            bucket.rounds += 1;
+            bucket.bet = bucket.bet.saturating_add(bet);
+            bucket.paid = bucket.paid.saturating_add(paid);
+        }
+        self.bet = self.bet.saturating_add(bet);
+        self.paid = self.paid.saturating_add(paid);
+
+        let limited = std::mem::take(&mut self.limited);
+        let step = self.target.step.min(self.target.max_adjust) as i64;
+        if self.in_band() {
+            // 回到范围内后逐局回落
+            self.bias -= self.bias.signum() * step.min(self.bias.abs());
+            return;
+        }
+        if limited {
+            return;
+        }
+        let max = self.target.max_adjust as i64;
+        let (raise, lower) = match self.target.divert {
+            RtpDivert::None => (true, true),
+            RtpDivert::Jackpot => {
+                let divert = self.divert_ratio(pot_ratio);
+                (divert > 0, divert < pot_ratio)
+            }
+        };
+        match self.rtp() {
+            Some(rtp) if rtp < self.target.target && raise => {
+                self.bias = (self.bias + step).min(max);
+            }
+            Some(rtp) if rtp > self.target.target && lower => {
+                self.bias = (self.bias - step).max(-max);
+            }
+            _ => {}
+        }
+    }
+
+    fn bucket_size(&self) -> u64 {
+        (self.target.window / RTP_BUCKETS).max(1)
+    }
+}
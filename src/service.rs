@@ -0,0 +1,254 @@
+use std::{
+    fs,
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
+};
+
+use tokio::{
+    sync::{mpsc, oneshot},
+    task::JoinHandle,
+};
+
+use crate::{
+    error::{PoolError, Result},
+    pool::{DrawOutcome, Pool, PoolSnapshot},
+    settlement::{Settlement, SettlementKind},
+    slots::fruit::{self, FruitBet, FruitDraw},
+};
+
+pub const DEFAULT_SERVICE_CAPACITY: usize = 256;
+
+/// 以 actor 方式运行 Pool 的服务配置，每个 Pool 在独立的 tokio 任务中串行处理命令
+#[derive(Debug, Clone)]
+pub struct PoolService {
+    pub capacity: usize,                // 命令队列长度，队列满时发送方等待
+    pub snapshot_path: Option<PathBuf>, // 关闭时写入最终快照的 JSON 文件
+}
+
+impl Default for PoolService {
+    fn default() -> Self {
+        Self {
+            capacity: DEFAULT_SERVICE_CAPACITY,
+            snapshot_path: None,
+        }
+    }
+}
+
+impl PoolService {
+    /// 在当前 tokio 运行时中启动 Pool，返回句柄及任务，任务结束时返回最终快照
+    ///
+    /// 调用 [`PoolHandle::shutdown`] 或所有句柄都被丢弃后，服务处理完队列中已有的命令，
+    /// 生成最终快照并按配置写入文件后退出；处理命令时 panic 的，不再处理后续命令，
+    /// 池子可能停在一局的中途，快照改为写入 `<snapshot_path>.poisoned` 以便排查，
+    /// 不覆盖上一次正常关闭的快照，任务返回 [`PoolError::Poisoned`]
+    pub fn spawn(self, pool: Pool) -> (PoolHandle, JoinHandle<Result<PoolSnapshot>>) {
+        let (sender, receiver) = mpsc::channel(self.capacity.max(1));
+        let id = pool.id;
+        let task = tokio::spawn(run(pool, receiver, self.snapshot_path));
+        (PoolHandle { id, sender }, task)
+    }
+}
+
+enum Command {
+    Draw {
+        bets: u64,
+        odds: u64,
+        jackpot: bool,
+        reply: oneshot::Sender<Result<DrawOutcome>>,
+    },
+    Fruit {
+        fruits: Vec<FruitBet>,
+        reply: oneshot::Sender<Result<FruitDraw>>,
+    },
+    Settle {
+        kind: SettlementKind,
+        amount: u64,
+        reply: oneshot::Sender<Result<Settlement>>,
+    },
+    Snapshot {
+        reply: oneshot::Sender<PoolSnapshot>,
+    },
+    Shutdown {
+        reply: oneshot::Sender<Result<PoolSnapshot>>,
+    },
+}
+
+/// 向 Pool 服务发送命令的句柄，可克隆后分发给多个任务，服务关闭后的命令返回
+/// [`PoolError::ServiceClosed`]
+#[derive(Debug, Clone)]
+pub struct PoolHandle {
+    id: u32,
+    sender: mpsc::Sender<Command>,
+}
+
+impl PoolHandle {
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// 执行一次下注，见 [`Pool::try_draw`]
+    pub async fn draw(&self, bets: u64, odds: u64) -> Result<DrawOutcome> {
+        self.request(|reply| Command::Draw {
+            bets,
+            odds,
+            jackpot: false,
+            reply,
+        })
+        .await?
+    }
+
+    /// 执行一次下注并判定彩金，见 [`Pool::try_draw_with_jackpot`]
+    pub async fn draw_with_jackpot(&self, bets: u64, odds: u64) -> Result<DrawOutcome> {
+        self.request(|reply| Command::Draw {
+            bets,
+            odds,
+            jackpot: true,
+            reply,
+        })
+        .await?
+    }
+
+    /// 水果机下注，出错时不修改池子状态，见 [`fruit::try_draw`]
+    pub async fn draw_fruit(&self, fruits: Vec<FruitBet>) -> Result<FruitDraw> {
+        self.request(|reply| Command::Fruit { fruits, reply })
+            .await?
+    }
+
+    /// 执行结算，见 [`Pool::settle`]
+    pub async fn settle(&self, kind: SettlementKind, amount: u64) -> Result<Settlement> {
+        self.request(|reply| Command::Settle {
+            kind,
+            amount,
+            reply,
+        })
+        .await?
+    }
+
+    pub async fn snapshot(&self) -> Result<PoolSnapshot> {
+        self.request(|reply| Command::Snapshot { reply }).await
+    }
+
+    /// 关闭服务，之后的命令不再接受；等待队列中已有的命令处理完毕，返回最终快照
+    pub async fn shutdown(&self) -> Result<PoolSnapshot> {
+        self.request(|reply| Command::Shutdown { reply }).await?
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.sender.is_closed()
+    }
+
+    async fn request<T>(&self, command: impl FnOnce(oneshot::Sender<T>) -> Command) -> Result<T> {
+        let (reply, response) = oneshot::channel();
+        self.sender
+            .send(command(reply))
+            .await
+            .map_err(|_| PoolError::ServiceClosed)?;
+        response.await.map_err(|_| PoolError::ServiceClosed)
+    }
+}
+
+async fn run(
+    mut pool: Pool,
+    mut receiver: mpsc::Receiver<Command>,
+    snapshot_path: Option<PathBuf>,
+) -> Result<PoolSnapshot> {
+    let mut shutdowns = Vec::new();
+    let mut poisoned = false;
+    // 关闭接收端后 recv 仍会返回队列中已有的命令，全部处理完才返回 None
+    while let Some(command) = receiver.recv().await {
+        match panic::catch_unwind(AssertUnwindSafe(|| execute(&mut pool, command))) {
+            Ok(Some(reply)) => {
+                receiver.close();
+                shutdowns.push(reply);
+            }
+            Ok(None) => {}
+            Err(_) => {
+                // 池子可能停在一局的中途，丢弃其余命令，只保留关闭请求
+                poisoned = true;
+                receiver.close();
+                while let Ok(command) = receiver.try_recv() {
+                    if let Command::Shutdown { reply } = command {
+                        shutdowns.push(reply);
+                    }
+                }
+                break;
+            }
+        }
+    }
+
+    let snapshot = pool.snapshot();
+    let path = match poisoned {
+        true => snapshot_path.map(quarantine_path),
+        false => snapshot_path,
+    };
+    let written = match path {
+        Some(path) => write_snapshot(path, snapshot.clone()).await,
+        None => Ok(()),
+    };
+    let result = match (poisoned, written) {
+        (true, _) => Err(PoolError::Poisoned),
+        (false, written) => written.map(|_| snapshot),
+    };
+    for reply in shutdowns {
+        let _ = reply.send(result.clone());
+    }
+    result
+}
+
+/// 执行一条命令，关闭请求返回其应答通道
+fn execute(pool: &mut Pool, command: Command) -> Option<oneshot::Sender<Result<PoolSnapshot>>> {
+    match command {
+        Command::Draw {
+            bets,
+            odds,
+            jackpot,
+            reply,
+        } => {
+            let outcome = if jackpot {
+                pool.try_draw_with_jackpot(bets, odds)
+            } else {
+                pool.try_draw(bets, odds)
+            };
+            let _ = reply.send(outcome);
+        }
+        Command::Fruit { fruits, reply } => {
+            let _ = reply.send(fruit::try_draw(fruits, pool));
+        }
+        Command::Settle {
+            kind,
+            amount,
+            reply,
+        } => {
+            let _ = reply.send(pool.settle(kind, amount));
+        }
+        Command::Snapshot { reply } => {
+            let _ = reply.send(pool.snapshot());
+        }
+        Command::Shutdown { reply } => return Some(reply),
+    }
+    None
+}
+
+/// 池子出错后快照的隔离路径，在原文件名后追加 `.poisoned`
+fn quarantine_path(path: PathBuf) -> PathBuf {
+    let mut name = path.into_os_string();
+    name.push(".poisoned");
+    PathBuf::from(name)
+}
+
+/// 在阻塞线程池中写入快照，不占用运行时的工作线程
+async fn write_snapshot(path: PathBuf, snapshot: PoolSnapshot) -> Result<()> {
+    let display = path.display().to_string();
+    tokio::task::spawn_blocking(move || write_snapshot_file(&path, &snapshot))
+        .await
+        .map_err(|e| PoolError::Io(format!("{}: {}", display, e)))?
+}
+
+/// 先写入临时文件再重命名，避免关闭过程中断时留下不完整的快照
+fn write_snapshot_file(path: &Path, snapshot: &PoolSnapshot) -> Result<()> {
+    let io_error = |e: &dyn std::fmt::Display| PoolError::Io(format!("{}: {}", path.display(), e));
+    let json = serde_json::to_string_pretty(snapshot).map_err(|e| io_error(&e))?;
+    let tmp = path.with_extension("tmp");
+    fs::write(&tmp, json).map_err(|e| io_error(&e))?;
+    fs::rename(&tmp, path).map_err(|e| io_error(&e))
+}
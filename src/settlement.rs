@@ -0,0 +1,126 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::{PoolError, Result},
+    pool::Pool,
+};
+
+/// 结算类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SettlementKind {
+    WithdrawBrokerage, // 提取佣金
+    RepayAdvance,      // 从池底归还垫分
+    InjectAdvance,     // 追加垫分
+}
+
+/// 结算记录，金额与 Pool::pot 同为万分比单位
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Settlement {
+    pub pool_id: u32,          // Pool ID
+    pub owner_id: u32,         // 所有者 ID
+    pub kind: SettlementKind,  // 结算类型
+    pub amount: u64,           // 结算金额
+    pub pot_before: u64,       // 结算前池底
+    pub pot_after: u64,        // 结算后池底
+    pub brokerage_before: u64, // 结算前佣金
+    pub brokerage_after: u64,  // 结算后佣金
+    pub advance_before: u64,   // 结算前垫分
+    pub advance_after: u64,    // 结算后垫分
+    pub timestamp: u64,        // 结算时间，Unix 秒
+}
+
+impl Pool {
+    /// 按结算类型执行结算
+    pub fn settle(&mut self, kind: SettlementKind, amount: u64) -> Result<Settlement> {
+        match kind {
+            SettlementKind::WithdrawBrokerage => self.withdraw_brokerage(amount),
+            SettlementKind::RepayAdvance => self.repay_advance(amount),
+            SettlementKind::InjectAdvance => self.inject_advance(amount),
+        }
+    }
+
+    /// 提取佣金，超过当前佣金时返回错误
+    pub fn withdraw_brokerage(&mut self, amount: u64) -> Result<Settlement> {
+        let brokerage = self
+            .brokerage
+            .checked_sub(amount)
+            .ok_or(PoolError::InsufficientFunds {
+                requested: amount,
+                available: self.brokerage,
+            })?;
+        self.notify(|observer, pool| {
+            observer.on_settle(pool, SettlementKind::WithdrawBrokerage, amount)
+        });
+        let before = self.ledger();
+        self.brokerage = brokerage;
+        Ok(self.settlement(SettlementKind::WithdrawBrokerage, amount, before))
+    }
+
+    /// 从池底归还垫分，池底不能低于底线，归还后重新生成波浪
+    pub fn repay_advance(&mut self, amount: u64) -> Result<Settlement> {
+        let available = self.advance.min(self.pot.saturating_sub(self.base_line));
+        if amount > available {
+            return Err(PoolError::InsufficientFunds {
+                requested: amount,
+                available,
+            });
+        }
+        self.notify(|observer, pool| {
+            observer.on_settle(pool, SettlementKind::RepayAdvance, amount)
+        });
+        let before = self.ledger();
+        self.pot -= amount;
+        self.advance -= amount;
+        self.reset_wave()?;
+        Ok(self.settlement(SettlementKind::RepayAdvance, amount, before))
+    }
+
+    /// 追加垫分到池底，追加后重新生成波浪
+    pub fn inject_advance(&mut self, amount: u64) -> Result<Settlement> {
+        let pot = self.pot.checked_add(amount).ok_or(PoolError::Overflow)?;
+        let advance = self
+            .advance
+            .checked_add(amount)
+            .ok_or(PoolError::Overflow)?;
+        self.notify(|observer, pool| {
+            observer.on_settle(pool, SettlementKind::InjectAdvance, amount)
+        });
+        let before = self.ledger();
+        self.pot = pot;
+        self.advance = advance;
+        self.reset_wave()?;
+        Ok(self.settlement(SettlementKind::InjectAdvance, amount, before))
+    }
+
+    fn ledger(&self) -> (u64, u64, u64) {
+        (self.pot, self.brokerage, self.advance)
+    }
+
+    fn settlement(
+        &self,
+        kind: SettlementKind,
+        amount: u64,
+        (pot_before, brokerage_before, advance_before): (u64, u64, u64),
+    ) -> Settlement {
+        let settlement = Settlement {
+            pool_id: self.id,
+            owner_id: self.owner_id,
+            kind,
+            amount,
+            pot_before,
+            pot_after: self.pot,
+            brokerage_before,
+            brokerage_after: self.brokerage,
+            advance_before,
+            advance_after: self.advance,
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0),
+        };
+        self.notify(|observer, pool| observer.on_settlement(pool, &settlement));
+        settlement
+    }
+}
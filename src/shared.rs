@@ -0,0 +1,143 @@
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc, Mutex, MutexGuard,
+};
+
+use crate::{
+    error::{PoolError, Result},
+    pool::{DrawOutcome, Pool, PoolSnapshot},
+    slots::fruit::{self, FruitBet, FruitDraw},
+};
+
+/// 多个玩家并发下注的 Pool 句柄，克隆得到的句柄指向同一个 Pool，可跨线程使用
+///
+/// 每次下注在锁内完整执行，同一 Pool 上的下注按加锁顺序串行；池底、彩金和佣金在每次修改后
+/// 发布到原子变量，读取时不加锁，可能落后于正在进行的下注
+///
+/// 持有锁的线程 panic 后池子状态可能只更新了一半，之后的 `try_` 方法返回 [`PoolError::Poisoned`]，
+/// 其余方法 panic
+#[derive(Debug, Clone)]
+pub struct SharedPool {
+    inner: Arc<SharedPoolInner>,
+}
+
+#[derive(Debug)]
+struct SharedPoolInner {
+    id: u32,
+    pool: Mutex<Pool>,
+    pot: AtomicU64,       // 池底，万分比单位
+    jackpot: AtomicU64,   // 彩金，万分比单位
+    brokerage: AtomicU64, // 佣金，万分比单位
+}
+
+impl SharedPool {
+    pub fn new(pool: Pool) -> Self {
+        SharedPool {
+            inner: Arc::new(SharedPoolInner {
+                id: pool.id,
+                pot: AtomicU64::new(pool.pot),
+                jackpot: AtomicU64::new(pool.jackpot),
+                brokerage: AtomicU64::new(pool.brokerage),
+                pool: Mutex::new(pool),
+            }),
+        }
+    }
+
+    pub fn id(&self) -> u32 {
+        self.inner.id
+    }
+
+    /// 当前池底，不加锁
+    pub fn pot(&self) -> u64 {
+        self.inner.pot.load(Ordering::Acquire)
+    }
+
+    /// 当前彩金，不加锁
+    pub fn jackpot(&self) -> u64 {
+        self.inner.jackpot.load(Ordering::Acquire)
+    }
+
+    /// 当前佣金，不加锁
+    pub fn brokerage(&self) -> u64 {
+        self.inner.brokerage.load(Ordering::Acquire)
+    }
+
+    /// 执行一次下注，见 [`Pool::draw`]
+    ///
+    /// # Panics
+    ///
+    /// 赔率为 0、金额溢出或锁已毒化时 panic，需要处理错误时请使用 [`SharedPool::try_draw`]
+    pub fn draw(&self, bets: u64, odds: u64) -> DrawOutcome {
+        self.with(|pool| pool.draw(bets, odds))
+    }
+
+    pub fn try_draw(&self, bets: u64, odds: u64) -> Result<DrawOutcome> {
+        self.try_with(|pool| pool.try_draw(bets, odds))?
+    }
+
+    /// 执行一次下注并判定彩金，见 [`Pool::draw_with_jackpot`]
+    ///
+    /// # Panics
+    ///
+    /// 赔率为 0、金额溢出或锁已毒化时 panic，需要处理错误时请使用
+    /// [`SharedPool::try_draw_with_jackpot`]
+    pub fn draw_with_jackpot(&self, bets: u64, odds: u64) -> DrawOutcome {
+        self.with(|pool| pool.draw_with_jackpot(bets, odds))
+    }
+
+    pub fn try_draw_with_jackpot(&self, bets: u64, odds: u64) -> Result<DrawOutcome> {
+        self.try_with(|pool| pool.try_draw_with_jackpot(bets, odds))?
+    }
+
+    /// 水果机下注，整局开奖在同一次加锁内完成，出错时不修改池子状态，见 [`fruit::try_draw`]
+    pub fn draw_fruit(&self, fruits: Vec<FruitBet>) -> Result<FruitDraw> {
+        self.try_with(|pool| fruit::try_draw(fruits, pool))?
+    }
+
+    pub fn snapshot(&self) -> Result<PoolSnapshot> {
+        Ok(self.lock()?.snapshot())
+    }
+
+    /// 加锁后修改 Pool，返回后重新发布池底、彩金和佣金，用于结算、更新比率等操作
+    ///
+    /// # Panics
+    ///
+    /// 锁已毒化时 panic，需要处理错误时请使用 [`SharedPool::try_with`]
+    pub fn with<T, F: FnOnce(&mut Pool) -> T>(&self, f: F) -> T {
+        self.try_with(f).expect("shared pool poisoned")
+    }
+
+    /// 同 [`SharedPool::with`]，锁已毒化时返回 [`PoolError::Poisoned`]
+    pub fn try_with<T, F: FnOnce(&mut Pool) -> T>(&self, f: F) -> Result<T> {
+        let mut pool = self.lock()?;
+        let result = f(&mut pool);
+        self.publish(&pool);
+        Ok(result)
+    }
+
+    /// 取回 Pool，仍有其他句柄时返回原句柄，锁已毒化时返回 [`PoolError::Poisoned`]
+    pub fn try_unwrap(self) -> std::result::Result<Result<Pool>, Self> {
+        Arc::try_unwrap(self.inner)
+            .map(|inner| inner.pool.into_inner().map_err(|_| PoolError::Poisoned))
+            .map_err(|inner| SharedPool { inner })
+    }
+
+    fn lock(&self) -> Result<MutexGuard<'_, Pool>> {
+        // 持有锁的线程 panic 时可能停在一局的中途，不再继续使用
+        self.inner.pool.lock().map_err(|_| PoolError::Poisoned)
+    }
+
+    fn publish(&self, pool: &Pool) {
+        self.inner.pot.store(pool.pot, Ordering::Release);
+        self.inner.jackpot.store(pool.jackpot, Ordering::Release);
+        self.inner
+            .brokerage
+            .store(pool.brokerage, Ordering::Release);
+    }
+}
+
+impl From<Pool> for SharedPool {
+    fn from(pool: Pool) -> Self {
+        SharedPool::new(pool)
+    }
+}
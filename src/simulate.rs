@@ -0,0 +1,497 @@
+use std::{
+    collections::BTreeMap,
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+};
+
+use rand::{seq::SliceRandom, Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::{
+    config::PoolConfig,
+    error::{PoolError, Result},
+    pool::{Pool, PoolRng, WaveState},
+    slots::fruit::{self, FruitBet},
+};
+
+/// 模拟时每局的下注策略
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum BetStrategy {
+    /// 每局以固定下注额和赔率调用 Pool::draw
+    Fixed { bets: u64, odds: u64 },
+    /// 每局从候选赔率中随机选择一个调用 Pool::draw
+    RandomOdds { bets: u64, odds: Vec<u64> },
+    /// 每局以同一组下注调用 fruit::draw
+    Fruit { bets: Vec<FruitBet> },
+}
+
+impl BetStrategy {
+    /// 检查下注策略，错误信息中注明出错的字段
+    pub fn check(&self) -> Result<()> {
+        let invalid = |reason: String| Err(PoolError::InvalidBet(reason));
+        match self {
+            BetStrategy::Fixed { odds: 0, .. } => invalid("odds must be positive".into()),
+            BetStrategy::Fixed { .. } => Ok(()),
+            BetStrategy::RandomOdds { odds, .. } => {
+                if odds.is_empty() {
+                    return invalid("odds must not be empty".into());
+                }
+                match odds.iter().position(|&odds| odds == 0) {
+                    Some(index) => invalid(format!("odds[{}] must be positive", index)),
+                    None => Ok(()),
+                }
+            }
+            BetStrategy::Fruit { bets } => {
+                if bets.is_empty() {
+                    return invalid("bets must not be empty".into());
+                }
+                for (index, bet) in bets.iter().enumerate() {
+                    if let Err(e) = bet.validate() {
+                        return invalid(format!("bets[{}].{}", index, e));
+                    }
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// 模拟参数
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimConfig {
+    pub rounds: u64,           // 模拟局数
+    pub strategy: BetStrategy, // 下注策略
+    #[serde(default)]
+    pub seed: u64, // 下注策略自身的随机种子，与 Pool 的随机数相互独立
+}
+
+/// 单一赔率的命中统计
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OddsStats {
+    pub draws: u64, // 下注次数
+    pub hits: u64,  // 命中次数
+    pub bet: u64,   // 下注额，下注单位
+    pub win: u64,   // 派奖额，下注单位
+}
+
+impl OddsStats {
+    fn merge(&mut self, other: &OddsStats) {
+        self.draws += other.draws;
+        self.hits += other.hits;
+        self.bet += other.bet;
+        self.win += other.win;
+    }
+}
+
+/// 模拟过程中累积的统计量，可与其他模拟的统计合并
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SimStats {
+    pub rounds: u64,                            // 局数
+    pub hit_rounds: u64,                        // 有派奖的局数
+    pub total_bet: u64,                         // 总下注，下注单位
+    pub total_win: u64,                         // 总派奖，下注单位
+    pub mean_return: f64,                       // 每局 派奖/下注 的均值
+    pub m2_return: f64,                         // 每局 派奖/下注 的离差平方和
+    pub pot_min: u64,                           // 池底最小值，万分比单位
+    pub pot_max: u64,                           // 池底最大值，万分比单位
+    pub max_drawdown: u64,                      // 池底最大回撤，万分比单位
+    pub brokerage: u64,                         // 期间产生的佣金，万分比单位
+    pub jackpot_contributed: u64,               // 期间累积的彩金，万分比单位
+    pub jackpot_paid: u64,                      // 派发的彩金，下注单位
+    pub jackpot_hits: u64,                      // 彩金中奖次数
+    pub wins_by_odds: BTreeMap<u64, OddsStats>, // 按赔率的命中分布
+    #[serde(skip)]
+    pot_peak: u64,
+}
+
+impl SimStats {
+    fn new(pot: u64) -> Self {
+        Self {
+            pot_min: pot,
+            pot_max: pot,
+            pot_peak: pot,
+            ..Default::default()
+        }
+    }
+
+    /// 记录一局的下注与派奖
+    fn record_round(&mut self, bet: u64, win: u64) {
+        self.rounds += 1;
+        self.total_bet += bet;
+        self.total_win += win;
+        if win > 0 {
+            self.hit_rounds += 1;
+        }
+        if bet > 0 {
+            let value = win as f64 / bet as f64;
+            let delta = value - self.mean_return;
+            self.mean_return += delta / self.rounds as f64;
+            self.m2_return += delta * (value - self.mean_return);
+        }
+    }
+
+    /// 记录一次单一赔率的下注结果
+    fn record_odds(&mut self, odds: u64, bet: u64, win: u64, hit: bool) {
+        let stats = self.wins_by_odds.entry(odds).or_default();
+        stats.draws += 1;
+        stats.bet += bet;
+        stats.win += win;
+        if hit {
+            stats.hits += 1;
+        }
+    }
+
+    /// 记录流入各类彩金的金额，万分比单位
+    fn record_contribution(&mut self, amount: u64) {
+        self.jackpot_contributed = self.jackpot_contributed.saturating_add(amount);
+    }
+
+    fn record_jackpot(&mut self, raw_amount: u64) {
+        self.jackpot_paid += raw_amount;
+        self.jackpot_hits += 1;
+    }
+
+    fn record_pot(&mut self, pot: u64) {
+        self.pot_min = self.pot_min.min(pot);
+        self.pot_max = self.pot_max.max(pot);
+        self.pot_peak = self.pot_peak.max(pot);
+        self.max_drawdown = self.max_drawdown.max(self.pot_peak - pot);
+    }
+
+    /// 合并另一组独立模拟的统计，池底相关指标取各自的极值
+    pub fn merge(&mut self, other: &SimStats) {
+        if other.rounds == 0 {
+            return;
+        }
+        if self.rounds == 0 {
+            *self = other.clone();
+            return;
+        }
+        let n1 = self.rounds as f64;
+        let n2 = other.rounds as f64;
+        let delta = other.mean_return - self.mean_return;
+        self.mean_return += delta * n2 / (n1 + n2);
+        self.m2_return += other.m2_return + delta * delta * n1 * n2 / (n1 + n2);
+
+        self.rounds += other.rounds;
+        self.hit_rounds += other.hit_rounds;
+        self.total_bet += other.total_bet;
+        self.total_win += other.total_win;
+        self.pot_min = self.pot_min.min(other.pot_min);
+        self.pot_max = self.pot_max.max(other.pot_max);
+        self.max_drawdown = self.max_drawdown.max(other.max_drawdown);
+        self.brokerage += other.brokerage;
+        self.record_contribution(other.jackpot_contributed);
+        self.jackpot_paid += other.jackpot_paid;
+        self.jackpot_hits += other.jackpot_hits;
+        for (odds, stats) in &other.wins_by_odds {
+            self.wins_by_odds.entry(*odds).or_default().merge(stats);
+        }
+    }
+
+    /// 派奖/下注，不含彩金
+    pub fn rtp(&self) -> f64 {
+        ratio(self.total_win, self.total_bet)
+    }
+
+    /// 有派奖的局数占比
+    pub fn hit_frequency(&self) -> f64 {
+        ratio(self.hit_rounds, self.rounds)
+    }
+
+    /// 每局 派奖/下注 的标准差
+    pub fn std_dev(&self) -> f64 {
+        if self.rounds < 2 {
+            return 0.0;
+        }
+        (self.m2_return / (self.rounds - 1) as f64).sqrt()
+    }
+
+    pub fn report(&self) -> SimReport {
+        SimReport {
+            rounds: self.rounds,
+            total_bet: self.total_bet,
+            total_win: self.total_win,
+            rtp: self.rtp(),
+            rtp_with_jackpot: ratio(self.total_win + self.jackpot_paid, self.total_bet),
+            hit_frequency: self.hit_frequency(),
+            std_dev: self.std_dev(),
+            pot_min: self.pot_min,
+            pot_max: self.pot_max,
+            max_drawdown: self.max_drawdown,
+            brokerage: self.brokerage,
+            jackpot_contributed: self.jackpot_contributed,
+            jackpot_paid: self.jackpot_paid,
+            jackpot_hits: self.jackpot_hits,
+            wins_by_odds: self.wins_by_odds.clone(),
+        }
+    }
+}
+
+/// 模拟报告
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimReport {
+    pub rounds: u64,                            // 局数
+    pub total_bet: u64,                         // 总下注，下注单位
+    pub total_win: u64,                         // 总派奖，下注单位
+    pub rtp: f64,                               // 返奖率，不含彩金
+    pub rtp_with_jackpot: f64,                  // 返奖率，含彩金
+    pub hit_frequency: f64,                     // 命中频率
+    pub std_dev: f64,                           // 每局 派奖/下注 的标准差
+    pub pot_min: u64,                           // 池底最小值，万分比单位
+    pub pot_max: u64,                           // 池底最大值，万分比单位
+    pub max_drawdown: u64,                      // 池底最大回撤，万分比单位
+    pub brokerage: u64,                         // 期间产生的佣金，万分比单位
+    pub jackpot_contributed: u64,               // 期间累积的彩金，万分比单位
+    pub jackpot_paid: u64,                      // 派发的彩金，下注单位
+    pub jackpot_hits: u64,                      // 彩金中奖次数
+    pub wins_by_odds: BTreeMap<u64, OddsStats>, // 按赔率的命中分布
+}
+
+/// 单局结束后的池子状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HistoryPoint {
+    pub round: u64,          // 局数，从 1 开始
+    pub pot: u64,            // 池底，万分比单位
+    pub segment: (u64, u64), // 当前分段
+    pub state: WaveState,    // 当前走势
+    pub hit: bool,           // 本局是否有派奖
+}
+
+/// 模拟过程中记录的池子轨迹
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PoolHistory {
+    pub start: u64,                // 起始池底，万分比单位
+    pub points: Vec<HistoryPoint>, // 每局的状态
+}
+
+impl PoolHistory {
+    /// 分段或走势发生变化的局
+    pub fn state_changes(&self) -> impl Iterator<Item = &HistoryPoint> {
+        self.points.windows(2).filter_map(|pair| {
+            let (prev, next) = (&pair[0], &pair[1]);
+            (prev.segment.1 != next.segment.1 || prev.state != next.state).then_some(next)
+        })
+    }
+}
+
+/// 按配置在 Pool 上运行模拟，返回统计结果
+pub fn run(pool: &mut Pool, config: &SimConfig) -> Result<SimStats> {
+    run_inner(pool, config, None)
+}
+
+/// 按配置在 Pool 上运行模拟，同时记录每局的池子轨迹
+pub fn run_with_history(pool: &mut Pool, config: &SimConfig) -> Result<(SimStats, PoolHistory)> {
+    let mut history = PoolHistory {
+        start: pool.pot,
+        points: Vec::with_capacity(config.rounds.min(1 << 20) as usize),
+    };
+    let stats = run_inner(pool, config, Some(&mut history))?;
+    Ok((stats, history))
+}
+
+fn run_inner(
+    pool: &mut Pool,
+    config: &SimConfig,
+    mut history: Option<&mut PoolHistory>,
+) -> Result<SimStats> {
+    config.strategy.check()?;
+    let mut rng = PoolRng::seed_from_u64(config.seed);
+    let mut stats = SimStats::new(pool.pot);
+    let brokerage = pool.brokerage;
+
+    for round in 1..=config.rounds {
+        let hit_rounds = stats.hit_rounds;
+        match &config.strategy {
+            BetStrategy::Fixed { bets, odds } => {
+                draw_round(pool, &mut stats, *bets, *odds)?;
+            }
+            BetStrategy::RandomOdds { bets, odds } => {
+                let odds = *odds.choose(&mut rng).ok_or(PoolError::InvalidOdds(0))?;
+                draw_round(pool, &mut stats, *bets, odds)?;
+            }
+            BetStrategy::Fruit { bets } => {
+                fruit_round(pool, &mut stats, bets.clone())?;
+            }
+        }
+        stats.record_pot(pool.pot);
+        if let Some(history) = history.as_deref_mut() {
+            history.points.push(HistoryPoint {
+                round,
+                pot: pool.pot,
+                segment: pool.get_segment(),
+                state: pool.get_state(),
+                hit: stats.hit_rounds > hit_rounds,
+            });
+        }
+    }
+
+    stats.brokerage = pool.brokerage.saturating_sub(brokerage);
+    Ok(stats)
+}
+
+/// 并行模拟参数
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParallelConfig {
+    pub replicas: usize, // 独立 Pool 副本数，每个副本各运行 SimConfig::rounds 局
+    #[serde(default)]
+    pub threads: usize, // 工作线程数，0 表示使用可用的 CPU 数
+    #[serde(default)]
+    pub seed: u64, // 主种子，各副本的种子由此派生
+}
+
+/// 置信区间
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ConfidenceInterval {
+    pub low: f64,
+    pub high: f64,
+}
+
+impl ConfidenceInterval {
+    fn around(mean: f64, std_err: f64) -> Self {
+        Self {
+            low: mean - Z_95 * std_err,
+            high: mean + Z_95 * std_err,
+        }
+    }
+}
+
+const Z_95: f64 = 1.96; // 95% 置信水平
+
+/// 并行模拟报告
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParallelReport {
+    pub report: SimReport,                    // 合并后的报告
+    pub rtp_ci: ConfidenceInterval,           // 返奖率 95% 置信区间，按副本间的差异估计
+    pub hit_frequency_ci: ConfidenceInterval, // 命中频率 95% 置信区间
+    pub replicas: Vec<SimReport>,             // 各副本的报告
+}
+
+/// 在多个线程上运行相互独立的 Pool 副本并合并统计
+///
+/// 每个副本的 Pool 种子与下注策略种子都由主种子派生，相同参数的结果可完全复现
+pub fn run_parallel(
+    pool_config: &PoolConfig,
+    config: &SimConfig,
+    parallel: &ParallelConfig,
+) -> Result<ParallelReport> {
+    pool_config.check()?;
+    config.strategy.check()?;
+    let mut master = PoolRng::seed_from_u64(parallel.seed);
+    let seeds: Vec<(u64, u64)> = (0..parallel.replicas)
+        .map(|_| (master.gen(), master.gen()))
+        .collect();
+    let threads = match parallel.threads {
+        0 => thread::available_parallelism().map_or(1, |n| n.get()),
+        n => n,
+    }
+    .min(parallel.replicas.max(1));
+
+    let next = AtomicUsize::new(0);
+    let results: Vec<Vec<(usize, Result<SimStats>)>> = thread::scope(|scope| {
+        let workers: Vec<_> = (0..threads)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut results = Vec::new();
+                    loop {
+                        let index = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(&(pool_seed, strategy_seed)) = seeds.get(index) else {
+                            break;
+                        };
+                        let config = SimConfig {
+                            seed: strategy_seed,
+                            ..config.clone()
+                        };
+                        let stats = Pool::from_config_with_seed(pool_config, pool_seed)
+                            .and_then(|mut pool| run(&mut pool, &config));
+                        results.push((index, stats));
+                    }
+                    results
+                })
+            })
+            .collect();
+        workers
+            .into_iter()
+            .map(|worker| worker.join().expect("simulation worker panicked"))
+            .collect()
+    });
+
+    // 按副本顺序合并，保证浮点结果与线程调度无关
+    let mut replicas: Vec<Option<SimStats>> = vec![None; parallel.replicas];
+    for (index, stats) in results.into_iter().flatten() {
+        replicas[index] = Some(stats?);
+    }
+    let replicas: Vec<SimStats> = replicas.into_iter().flatten().collect();
+
+    let mut merged = SimStats::default();
+    for stats in &replicas {
+        merged.merge(stats);
+    }
+    let report = merged.report();
+    Ok(ParallelReport {
+        rtp_ci: rtp_interval(&replicas, &merged),
+        hit_frequency_ci: proportion_interval(report.hit_frequency, report.rounds),
+        replicas: replicas.iter().map(SimStats::report).collect(),
+        report,
+    })
+}
+
+/// 以各副本返奖率的样本方差估计合并返奖率的置信区间，只有一个副本时退化为按局估计
+fn rtp_interval(replicas: &[SimStats], merged: &SimStats) -> ConfidenceInterval {
+    let rtp = merged.rtp();
+    if replicas.len() < 2 {
+        let std_err = merged.std_dev() / (merged.rounds.max(1) as f64).sqrt();
+        return ConfidenceInterval::around(rtp, std_err);
+    }
+    let k = replicas.len() as f64;
+    let mean = replicas.iter().map(SimStats::rtp).sum::<f64>() / k;
+    let variance = replicas
+        .iter()
+        .map(|stats| (stats.rtp() - mean).powi(2))
+        .sum::<f64>()
+        / (k - 1.0);
+    ConfidenceInterval::around(rtp, (variance / k).sqrt())
+}
+
+/// 比例的正态近似置信区间
+fn proportion_interval(p: f64, n: u64) -> ConfidenceInterval {
+    let n = n.max(1) as f64;
+    ConfidenceInterval::around(p, (p * (1.0 - p) / n).sqrt())
+}
+
+fn draw_round(pool: &mut Pool, stats: &mut SimStats, bets: u64, odds: u64) -> Result<()> {
+    let outcome = pool.try_draw_with_jackpot(bets, odds)?;
+    stats.record_odds(odds, bets, outcome.raw_reward, outcome.hit);
+    stats.record_round(bets, outcome.raw_reward);
+    stats.record_contribution(outcome.jackpot_contribution);
+    if let Some(award) = outcome.jackpot {
+        stats.record_jackpot(award.raw_amount);
+    }
+    Ok(())
+}
+
+fn fruit_round(pool: &mut Pool, stats: &mut SimStats, bets: Vec<FruitBet>) -> Result<()> {
+    let result = fruit::try_draw(bets, pool)?;
+    let mut bet = 0;
+    let mut win = 0;
+    for reward in &result.rewards {
+        bet += reward.bet;
+        win += reward.reward;
+        stats.record_odds(reward.odds, reward.bet, reward.reward, reward.flag);
+        stats.record_contribution(reward.jackpot_contribution);
+        if let Some(award) = reward.jackpot {
+            stats.record_jackpot(award.raw_amount);
+        }
+    }
+    stats.record_round(bet, win);
+    Ok(())
+}
+
+fn ratio(numerator: u64, denominator: u64) -> f64 {
+    match denominator {
+        0 => 0.0,
+        _ => numerator as f64 / denominator as f64,
+    }
+}
@@ -1,8 +1,12 @@
-use rand::{rngs::StdRng, seq::SliceRandom, Rng};
+use rand::{seq::SliceRandom, Rng};
 use serde::{Deserialize, Serialize};
 use validator::Validate;
 
-use crate::pool::Pool;
+use crate::{
+    error::{PoolError, Result},
+    jackpot::JackpotAward,
+    pool::{DrawOutcome, Pool},
+};
 
 const BAR_HIGH_ODDS: u8 = 100;
 const BAR_MEDIUM_ODDS: u8 = 50;
@@ -65,7 +69,7 @@ impl GeneralLevel {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize, Hash, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Hash, PartialEq, Eq)]
 pub enum FruitSymbol {
     Bar,
     LuckySeven,
@@ -101,7 +105,7 @@ impl FruitSymbol {
         }
     }
 
-    fn get_position(&self, level: &GeneralLevel, rng: &mut StdRng) -> u8 {
+    fn get_position<R: Rng + ?Sized>(&self, level: &GeneralLevel, rng: &mut R) -> u8 {
         match self {
             FruitSymbol::Bar => match level {
                 GeneralLevel::High => BAR_POSITION_HIGH,
@@ -151,7 +155,7 @@ impl FruitSymbol {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize, Validate)]
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
 pub struct FruitBet {
     pub symbol: FruitSymbol,
     #[validate(range(min = 1, max = 100, message = "Amount must be between 1 and 100"))]
@@ -159,18 +163,22 @@ pub struct FruitBet {
 }
 
 impl FruitBet {
-    fn draw(&self, level: &GeneralLevel, pool: &mut Pool) -> (bool, u64, Option<u8>) {
+    fn try_draw(&self, level: &GeneralLevel, pool: &mut Pool) -> Result<(DrawOutcome, Option<u8>)> {
         let odds = self.symbol.get_odds(level) as u64;
-        let (flag, reward) = pool.draw(self.value as u64, odds);
-        let position = match flag {
+        let outcome = pool.try_draw_with_jackpot(self.value as u64, odds)?;
+        let position = match outcome.hit {
             true => Some(get_furit_postition(&self.symbol, level, pool.get_mut_rng())),
             false => None,
         };
-        (flag, reward, position)
+        Ok((outcome, position))
     }
 }
 
-fn get_furit_postition(symbol: &FruitSymbol, level: &GeneralLevel, rng: &mut StdRng) -> u8 {
+fn get_furit_postition<R: Rng + ?Sized>(
+    symbol: &FruitSymbol,
+    level: &GeneralLevel,
+    rng: &mut R,
+) -> u8 {
     symbol.get_position(level, rng)
 }
 
@@ -178,17 +186,33 @@ fn get_furit_postition(symbol: &FruitSymbol, level: &GeneralLevel, rng: &mut Std
 pub struct FruitReward {
     pub symbol: FruitSymbol,
     pub bet: u64,
+    #[serde(default)]
+    pub odds: u64, // 本局该符号的赔率，未命中时同样记录
     pub reward: u64,
     pub flag: bool,
+    pub jackpot: Option<JackpotAward>,
+    #[serde(default)]
+    pub jackpot_contribution: u64, // 流入各类彩金的金额，万分比单位
 }
 
 impl FruitReward {
-    fn new(symbol: FruitSymbol, bet: u64, reward: u64, flag: bool) -> Self {
+    fn new(
+        symbol: FruitSymbol,
+        bet: u64,
+        odds: u64,
+        reward: u64,
+        flag: bool,
+        jackpot: Option<JackpotAward>,
+        jackpot_contribution: u64,
+    ) -> Self {
         Self {
             symbol,
             bet,
+            odds,
             reward,
             flag,
+            jackpot,
+            jackpot_contribution,
         }
     }
 }
@@ -200,11 +224,26 @@ pub struct FruitDraw {
     pub odds: u8,
 }
 
+/// 水果机开奖，不检查下注金额的范围
+///
+/// # Panics
+///
+/// 金额溢出时 panic，需要处理错误时请使用 [`try_draw`]
 pub fn draw(fruits: Vec<FruitBet>, pool: &mut Pool) -> FruitDraw {
+    play(fruits, pool).expect("invalid fruit bet")
+}
+
+/// 水果机开奖，开奖前按各符号的最高赔率检查整组下注，下注不合法或金额溢出时返回错误且不修改池子状态
+pub fn try_draw(fruits: Vec<FruitBet>, pool: &mut Pool) -> Result<FruitDraw> {
+    check_bets(&fruits, pool)?;
+    play(fruits, pool)
+}
+
+fn play(fruits: Vec<FruitBet>, pool: &mut Pool) -> Result<FruitDraw> {
     let level = random_level(pool.get_mut_rng()); // 获取一次 level
     let mut positions = Vec::new();
     let mut missed = full_symbol();
-    let rewards = calculate_rewards(fruits, pool, &level, &mut positions, &mut missed);
+    let rewards = calculate_rewards(fruits, pool, &level, &mut positions, &mut missed)?;
     let new_positions = match positions.len() {
         0 => match missed.len() {
             0 => vec![NONE],
@@ -216,14 +255,27 @@ pub fn draw(fruits: Vec<FruitBet>, pool: &mut Pool) -> FruitDraw {
             positions
         }
     };
-    FruitDraw {
+    Ok(FruitDraw {
         rewards,
         positions: new_positions,
         odds: level.get_position(),
+    })
+}
+
+/// 检查整组下注的金额范围，并按各符号的最高赔率检查是否会溢出
+fn check_bets(fruits: &[FruitBet], pool: &Pool) -> Result<()> {
+    let mut draws = Vec::with_capacity(fruits.len());
+    for fruit in fruits {
+        fruit
+            .validate()
+            .map_err(|e| PoolError::InvalidBet(e.to_string()))?;
+        let odds = fruit.symbol.get_odds(&GeneralLevel::High) as u64;
+        draws.push((fruit.value as u64, odds));
     }
+    pool.check_draws(&draws)
 }
 
-pub fn random_level(rng: &mut StdRng) -> GeneralLevel {
+pub fn random_level<R: Rng + ?Sized>(rng: &mut R) -> GeneralLevel {
     match rng.gen_range(0..10) {
         0..=4 => match rng.gen_range(0..3) {
             0 => GeneralLevel::High,
@@ -257,22 +309,30 @@ fn calculate_rewards(
     level: &GeneralLevel,    // 假设 random_level 返回的类型为 LevelType
     positions: &mut Vec<u8>, // 假设 positions 是 u8 类型
     missed: &mut Vec<FruitSymbol>,
-) -> Vec<FruitReward> {
+) -> Result<Vec<FruitReward>> {
     fruits.shuffle(pool.get_mut_rng());
     fruits
         .into_iter()
         .map(|bet| {
-            let (flag, reward, position) = bet.draw(level, pool);
+            let (outcome, position) = bet.try_draw(level, pool)?;
             if let Some(p) = position {
                 positions.push(p);
             }
             remove_symbol(missed, &bet.symbol); // 从 missed 中删除符号
-            FruitReward::new(bet.symbol, bet.value as u64, reward, flag)
+            Ok(FruitReward::new(
+                bet.symbol,
+                bet.value as u64,
+                outcome.odds,
+                outcome.raw_reward,
+                outcome.hit,
+                outcome.jackpot,
+                outcome.jackpot_contribution,
+            ))
         })
         .collect()
 }
 
-fn get_missed_position(missed: &Vec<FruitSymbol>, rng: &mut StdRng) -> Vec<u8> {
+fn get_missed_position<R: Rng + ?Sized>(missed: &[FruitSymbol], rng: &mut R) -> Vec<u8> {
     let symbol = missed.choose(rng).unwrap();
     let level = match rng.gen_range(0..2) {
         0 => GeneralLevel::Minimal,
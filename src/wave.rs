@@ -1,4 +1,18 @@
-use rand::{rngs::StdRng, seq::SliceRandom, Rng};
+use std::{collections::VecDeque, fmt::Debug};
+
+use rand::{seq::SliceRandom, Rng, RngCore, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::{PoolError, Result},
+    pool::PoolRng,
+};
+
+mod analysis;
+mod record;
+
+pub use analysis::{analyze, analyze_raw, AmplitudeStats, RatioBounds, WaveAnalysis, WaveIssue};
+pub use record::WaveRecord;
 
 const GOLD_LESS: [f64; 11] = [
     0.382, 0.382, 0.5, 0.5, 0.5, 0.618, 0.618, 0.618, 0.764, 0.764, 0.764,
@@ -7,66 +21,414 @@ const GOLD_MORE: [f64; 3] = [1.0, 1.309, 1.618];
 // const GOLD_FIVE: [f64; 4] = [0.764, 1.0, 1.309, 1.618];
 const GOLD_ADJST_MORE: [f64; 3] = [1.0, 1.309, 1.618];
 const GOLD_ADJST_LESS: [f64; 9] = [0.618, 0.618, 0.764, 0.764, 0.764, 1.0, 1.0, 1.0, 1.171];
+const DRIVING_COUNT: usize = 5;
+const ADJUSTMENT_COUNT: usize = 3;
+const DEPTH: u32 = 3;
 
-pub fn create_wave(pot: u64, baseline: u64, boundary: u64, rng: &mut StdRng) -> Vec<u64> {
-    let down = pot - baseline;
-    let up = if pot > boundary { 0 } else { boundary - pot };
-    let rand = rand::thread_rng().gen_range(0..(down + up));
+/// 单条波浪的分段数上限，分段数随浪数按细分层数成指数增长
+pub const MAX_SEGMENTS: usize = 1 << 20;
 
-    let wave: Vec<f64> = if rand < up {
-        span_wave(pot as f64, boundary as f64, rng)
-    } else {
-        span_wave(pot as f64, baseline as f64, rng)
-    };
+/// 黄金分割波浪的形态参数，默认值即原有的常量
+///
+/// 奇数位的浪取 `*_more` 中的系数向目标推进，偶数位的浪取 `*_less` 中的系数反向回撤，
+/// 系数表中重复的值用于提高该系数被抽中的概率
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WaveParams {
+    pub driving_more: Vec<f64>,    // 推动浪的推进系数
+    pub driving_less: Vec<f64>,    // 推动浪的回撤系数
+    pub adjustment_more: Vec<f64>, // 调整浪的推进系数
+    pub adjustment_less: Vec<f64>, // 调整浪的回撤系数
+    pub driving_count: usize,      // 推动浪的浪数
+    pub adjustment_count: usize,   // 调整浪的浪数
+    pub depth: u32,                // 细分层数
+}
+
+impl Default for WaveParams {
+    fn default() -> Self {
+        Self {
+            driving_more: GOLD_MORE.to_vec(),
+            driving_less: GOLD_LESS.to_vec(),
+            adjustment_more: GOLD_ADJST_MORE.to_vec(),
+            adjustment_less: GOLD_ADJST_LESS.to_vec(),
+            driving_count: DRIVING_COUNT,
+            adjustment_count: ADJUSTMENT_COUNT,
+            depth: DEPTH,
+        }
+    }
+}
+
+impl WaveParams {
+    /// 校验参数，系数必须为正的有限值，且最坏情况下各浪系数之和仍为正
+    pub fn check(&self) -> Result<()> {
+        check_table("driving_more", &self.driving_more)?;
+        check_table("driving_less", &self.driving_less)?;
+        check_table("adjustment_more", &self.adjustment_more)?;
+        check_table("adjustment_less", &self.adjustment_less)?;
+        if self.driving_count == 0 || self.adjustment_count == 0 {
+            return Err(PoolError::InvalidWaveParams(String::from(
+                "wave counts must be positive",
+            )));
+        }
+        let widest = self.driving_count.max(self.adjustment_count);
+        match widest.checked_pow(self.depth + 1) {
+            Some(segments) if segments <= MAX_SEGMENTS => {}
+            _ => {
+                return Err(PoolError::InvalidWaveParams(format!(
+                    "{} waves at depth {} may exceed {} segments",
+                    widest, self.depth, MAX_SEGMENTS
+                )))
+            }
+        }
+        check_sum(
+            "driving",
+            self.driving_count,
+            &self.driving_more,
+            &self.driving_less,
+        )?;
+        check_sum(
+            "adjustment",
+            self.adjustment_count,
+            &self.adjustment_more,
+            &self.adjustment_less,
+        )
+    }
+}
 
-    // 将 `Vec<f64>` 转换为 `Vec<u64>`
-    wave.into_iter().map(|v| v.round() as u64).collect()
+fn check_table(name: &str, table: &[f64]) -> Result<()> {
+    if table.is_empty() {
+        return Err(PoolError::InvalidWaveParams(format!("{} is empty", name)));
+    }
+    if let Some(value) = table.iter().find(|v| !(v.is_finite() && **v > 0.0)) {
+        return Err(PoolError::InvalidWaveParams(format!(
+            "{} contains non-positive coefficient {}",
+            name, value
+        )));
+    }
+    Ok(())
 }
 
-pub fn create_segment(waves: &mut Vec<u64>, pot: u64) -> (u64, u64) {
-    if let Some(wave) = waves.first().cloned() {
-        waves.remove(0);
-        (pot, wave)
+/// 推进取最小系数、回撤取最大系数时，系数之和仍须为正，否则波浪会反向或除零
+fn check_sum(name: &str, count: usize, more: &[f64], less: &[f64]) -> Result<()> {
+    let min_more = more.iter().cloned().fold(f64::INFINITY, f64::min);
+    let max_less = less.iter().cloned().fold(0.0, f64::max);
+    let pushes = count.div_ceil(2) as f64;
+    let pulls = (count / 2) as f64;
+    let sum = pushes * min_more - pulls * max_less;
+    if sum > 0.0 && sum.is_finite() {
+        Ok(())
     } else {
-        (pot, 0)
+        Err(PoolError::InvalidWaveParams(format!(
+            "{} coefficients may sum to {}",
+            name, sum
+        )))
+    }
+}
+
+/// 波浪生成器，从当前池底出发生成一串分段目标供 Pool 依次消费
+///
+/// Pool 通过 [`WaveStream`] 惰性消费波浪：先由 `legs` 生成首层，消费到某段时再由 `subdivide` 细分，
+/// 只实现 `generate` 的生成器会一次性生成全部目标
+pub trait WaveGenerator: Debug + Send + Sync {
+    /// 一次性生成整条波浪，所有随机选择都必须来自传入的 rng 以保证可复现
+    fn generate(
+        &self,
+        pot: u64,
+        baseline: u64,
+        boundary: u64,
+        rng: &mut dyn RngCore,
+    ) -> Result<Vec<u64>>;
+
+    /// 生成波浪的首层，默认即 `generate` 的全部目标
+    fn legs(
+        &self,
+        pot: u64,
+        baseline: u64,
+        boundary: u64,
+        rng: &mut dyn RngCore,
+    ) -> Result<Vec<WaveLeg>> {
+        let waves = self.generate(pot, baseline, boundary, rng)?;
+        Ok(waves
+            .into_iter()
+            .scan(pot, |from, to| {
+                Some(WaveLeg::target(std::mem::replace(from, to), to))
+            })
+            .collect())
+    }
+
+    /// 将 `depth` 大于 0 的浪细分为下一层，默认直接取其终点
+    fn subdivide(&self, leg: &WaveLeg, _rng: &mut dyn RngCore) -> Vec<WaveLeg> {
+        vec![WaveLeg { depth: 0, ..*leg }]
+    }
+}
+
+/// 默认的黄金分割波浪生成器，按推动浪与调整浪逐级细分
+#[derive(Debug, Clone, Default)]
+pub struct GoldenWave {
+    params: WaveParams,
+}
+
+impl GoldenWave {
+    /// 使用指定的形态参数，参数不合法时返回错误
+    pub fn new(params: WaveParams) -> Result<Self> {
+        params.check()?;
+        Ok(Self { params })
+    }
+
+    pub fn params(&self) -> &WaveParams {
+        &self.params
+    }
+}
+
+impl WaveGenerator for GoldenWave {
+    fn generate(
+        &self,
+        pot: u64,
+        baseline: u64,
+        boundary: u64,
+        rng: &mut dyn RngCore,
+    ) -> Result<Vec<u64>> {
+        create_wave_with_params(pot, baseline, boundary, &self.params, rng)
+    }
+
+    fn legs(
+        &self,
+        pot: u64,
+        baseline: u64,
+        boundary: u64,
+        rng: &mut dyn RngCore,
+    ) -> Result<Vec<WaveLeg>> {
+        golden_legs(pot, baseline, boundary, &self.params, rng)
     }
+
+    fn subdivide(&self, leg: &WaveLeg, rng: &mut dyn RngCore) -> Vec<WaveLeg> {
+        golden_subdivide(leg, &self.params, rng)
+    }
+}
+
+/// 波浪中尚未展开的一段浪，`depth` 为还需细分的层数，为 0 时 `to` 即分段目标
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct WaveLeg {
+    pub from: f64,  // 起点
+    pub to: f64,    // 终点
+    pub depth: u32, // 剩余细分层数
+}
+
+impl WaveLeg {
+    /// 已确定的分段目标
+    pub fn target(from: u64, to: u64) -> Self {
+        Self {
+            from: from as f64,
+            to: to as f64,
+            depth: 0,
+        }
+    }
+
+    fn value(&self) -> u64 {
+        self.to.round() as u64
+    }
+}
+
+/// 惰性展开的波浪，消费到哪一段才细分到哪一段，内存只与细分层数和浪数有关
+///
+/// 展开使用独立的随机数，预读后续目标不会改变开奖结果
+#[derive(Debug, Clone)]
+pub struct WaveStream {
+    targets: VecDeque<u64>, // 已展开尚未消费的分段目标
+    legs: Vec<WaveLeg>,     // 尚未展开的浪，栈顶为紧接 targets 之后的一段
+    rng: PoolRng,           // 展开使用的随机数
 }
 
-fn span_wave(from: f64, to: f64, rng: &mut StdRng) -> Vec<f64> {
-    let len = to - from;
-    let wave = generate_wave(len, rng);
-    wave.into_iter()
-        .scan(from, |acc, x| {
-            let point = *acc + x;
-            *acc = point;
-            Some(point)
+impl WaveStream {
+    /// 从 rng 派生展开用的随机数，并由生成器生成波浪的首层
+    pub fn generate<R: RngCore + ?Sized>(
+        generator: &dyn WaveGenerator,
+        pot: u64,
+        baseline: u64,
+        boundary: u64,
+        rng: &mut R,
+    ) -> Result<Self> {
+        let mut seed = <PoolRng as SeedableRng>::Seed::default();
+        rng.fill_bytes(&mut seed);
+        let mut rng = PoolRng::from_seed(seed);
+        let mut legs = generator.legs(pot, baseline, boundary, &mut rng)?;
+        legs.reverse();
+        Ok(WaveStream {
+            targets: VecDeque::new(),
+            legs,
+            rng,
         })
-        .collect()
+    }
+
+    /// 由已确定的分段目标组成的波浪
+    pub fn from_targets<I: IntoIterator<Item = u64>>(targets: I) -> Self {
+        Self::from_parts(targets, Vec::new(), PoolRng::seed_from_u64(0))
+    }
+
+    /// 由快照中的各部分还原波浪，`legs` 为栈序，末尾为下一段
+    pub fn from_parts<I: IntoIterator<Item = u64>>(
+        targets: I,
+        legs: Vec<WaveLeg>,
+        rng: PoolRng,
+    ) -> Self {
+        WaveStream {
+            targets: targets.into_iter().collect(),
+            legs,
+            rng,
+        }
+    }
+
+    /// 已展开尚未消费的分段目标
+    pub fn targets(&self) -> &VecDeque<u64> {
+        &self.targets
+    }
+
+    /// 尚未展开的浪，栈序，末尾为下一段
+    pub fn legs(&self) -> &[WaveLeg] {
+        &self.legs
+    }
+
+    /// 展开使用的随机数状态
+    pub fn rng(&self) -> &PoolRng {
+        &self.rng
+    }
+
+    /// 取出下一个分段目标，波浪耗尽时返回 None
+    pub fn next(&mut self, generator: &dyn WaveGenerator) -> Option<u64> {
+        if self.targets.is_empty() {
+            self.expand(generator);
+        }
+        self.targets.pop_front()
+    }
+
+    /// 预读接下来最多 `n` 个分段目标，不消费
+    pub fn peek(&mut self, generator: &dyn WaveGenerator, n: usize) -> Vec<u64> {
+        while self.targets.len() < n && self.expand(generator) {}
+        self.targets.iter().take(n).cloned().collect()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.targets.is_empty() && self.legs.is_empty()
+    }
+
+    /// 已展开尚未消费的目标数与尚未展开的浪数之和，是剩余分段数的下限
+    pub fn len_hint(&self) -> usize {
+        self.targets.len() + self.legs.len()
+    }
+
+    /// 展开栈顶的浪直到得到一个新的分段目标，没有可展开的浪时返回 false
+    fn expand(&mut self, generator: &dyn WaveGenerator) -> bool {
+        while let Some(leg) = self.legs.pop() {
+            if leg.depth == 0 {
+                self.targets.push_back(leg.value());
+                return true;
+            }
+            let sub_legs = generator.subdivide(&leg, &mut self.rng);
+            self.legs.extend(sub_legs.into_iter().rev());
+        }
+        false
+    }
 }
 
-fn generate_wave(len: f64, rng: &mut StdRng) -> Vec<f64> {
-    let ratios = driving_wave(5);
-    let lens = ratio_to_len(len, ratios);
-    create_level_wave(lens, 3, rng)
+/// 从当前池底出发生成一条波浪，池底在底线与边界线之间无可用空间时返回错误
+pub fn create_wave<R: Rng + ?Sized>(
+    pot: u64,
+    baseline: u64,
+    boundary: u64,
+    rng: &mut R,
+) -> Result<Vec<u64>> {
+    create_wave_with_params(pot, baseline, boundary, &WaveParams::default(), rng)
 }
 
-fn create_level_wave(lens: Vec<f64>, level: u32, rng: &mut StdRng) -> Vec<f64> {
-    if level == 0 {
-        return lens;
+/// 按指定的形态参数一次性生成整条波浪，参数需事先通过 [`WaveParams::check`] 校验
+pub fn create_wave_with_params<R: Rng + ?Sized>(
+    pot: u64,
+    baseline: u64,
+    boundary: u64,
+    params: &WaveParams,
+    rng: &mut R,
+) -> Result<Vec<u64>> {
+    let waves = create_raw_wave_with_params(pot, baseline, boundary, params, rng)?;
+    Ok(waves.into_iter().map(|v| v.round() as u64).collect())
+}
+
+/// 与 [`create_wave_with_params`] 相同但不取整，用于 [`analyze_raw`] 检查参数产生的 NaN 等异常
+pub fn create_raw_wave_with_params<R: Rng + ?Sized>(
+    pot: u64,
+    baseline: u64,
+    boundary: u64,
+    params: &WaveParams,
+    rng: &mut R,
+) -> Result<Vec<f64>> {
+    let mut legs = golden_legs(pot, baseline, boundary, params, rng)?;
+    legs.reverse();
+    let mut waves = Vec::new();
+    while let Some(leg) = legs.pop() {
+        if leg.depth == 0 {
+            waves.push(leg.to);
+        } else {
+            legs.extend(golden_subdivide(&leg, params, rng).into_iter().rev());
+        }
     }
-    let mut new_lens = Vec::new();
-    for l in lens {
-        let sub_wave = create_sub_wave(l, rng);
-        new_lens.extend(sub_wave);
+    Ok(waves)
+}
+
+/// 黄金分割波浪的首层推动浪
+fn golden_legs<R: Rng + ?Sized>(
+    pot: u64,
+    baseline: u64,
+    boundary: u64,
+    params: &WaveParams,
+    rng: &mut R,
+) -> Result<Vec<WaveLeg>> {
+    let down = pot.saturating_sub(baseline);
+    let up = boundary.saturating_sub(pot);
+    let range = down.checked_add(up).ok_or(PoolError::Overflow)?;
+    if range == 0 {
+        return Err(PoolError::EmptyWaveRange {
+            pot,
+            base_line: baseline,
+            boundary,
+        });
     }
-    create_level_wave(new_lens, level - 1, rng)
+    let rand = rng.gen_range(0..range);
+    let to = if rand < up { boundary } else { baseline };
+
+    let from = pot as f64;
+    let ratios = driving_wave(params, rng);
+    let lens = ratio_to_len(to as f64 - from, ratios);
+    Ok(span_legs(from, lens, params.depth))
+}
+
+/// 将一段浪细分为推动浪或调整浪
+fn golden_subdivide<R: Rng + ?Sized>(
+    leg: &WaveLeg,
+    params: &WaveParams,
+    rng: &mut R,
+) -> Vec<WaveLeg> {
+    let lens = create_sub_wave(leg.to - leg.from, params, rng);
+    span_legs(leg.from, lens, leg.depth.saturating_sub(1))
+}
+
+fn span_legs(from: f64, lens: Vec<f64>, depth: u32) -> Vec<WaveLeg> {
+    lens.into_iter()
+        .scan(from, |acc, len| {
+            let leg = WaveLeg {
+                from: *acc,
+                to: *acc + len,
+                depth,
+            };
+            *acc = leg.to;
+            Some(leg)
+        })
+        .collect()
 }
 
-fn create_sub_wave(len: f64, rng: &mut StdRng) -> Vec<f64> {
-    let ratios = if rand::random::<bool>() {
-        driving_wave(5)
+fn create_sub_wave<R: Rng + ?Sized>(len: f64, params: &WaveParams, rng: &mut R) -> Vec<f64> {
+    let ratios = if rng.gen::<bool>() {
+        driving_wave(params, rng)
     } else {
-        adjustment_wave(rng)
+        adjustment_wave(params, rng)
     };
     ratio_to_len(len, ratios)
 }
@@ -77,39 +439,38 @@ fn ratio_to_len(len: f64, ratios: Vec<f64>) -> Vec<f64> {
     ratios.into_iter().map(|r| r * base * len).collect()
 }
 
-fn driving_wave(n: usize) -> Vec<f64> {
-    let coefficients = span_driving_coefficient(n);
+fn driving_wave<R: Rng + ?Sized>(params: &WaveParams, rng: &mut R) -> Vec<f64> {
+    let coefficients = span_coefficient(
+        params.driving_count,
+        &params.driving_more,
+        &params.driving_less,
+        rng,
+    );
     span_ratio(coefficients)
 }
 
-fn span_driving_coefficient(n: usize) -> Vec<f64> {
-    let mut coefficients = Vec::new();
-    // let mut last_wave = 1.0;
-    for i in 1..=n {
-        let ratio = if i % 2 == 1 {
-            *GOLD_MORE.choose(&mut rand::thread_rng()).unwrap()
-        } else {
-            -*GOLD_LESS.choose(&mut rand::thread_rng()).unwrap()
-            // -last_wave * *GOLD_LESS.choose(&mut rand::thread_rng()).unwrap()
-        };
-        // last_wave = ratio;
-        coefficients.push(ratio);
-    }
-    coefficients
-}
-
-fn adjustment_wave(rng: &mut StdRng) -> Vec<f64> {
-    let coefficients = span_adjustment_coefficient(3, rng);
+fn adjustment_wave<R: Rng + ?Sized>(params: &WaveParams, rng: &mut R) -> Vec<f64> {
+    let coefficients = span_coefficient(
+        params.adjustment_count,
+        &params.adjustment_more,
+        &params.adjustment_less,
+        rng,
+    );
     span_ratio(coefficients)
 }
 
-fn span_adjustment_coefficient(n: usize, rng: &mut StdRng) -> Vec<f64> {
+fn span_coefficient<R: Rng + ?Sized>(
+    n: usize,
+    more: &[f64],
+    less: &[f64],
+    rng: &mut R,
+) -> Vec<f64> {
     let mut coefficients = Vec::new();
     for i in 1..=n {
         let ratio = if i % 2 == 1 {
-            *GOLD_ADJST_MORE.choose(rng).unwrap()
+            *more.choose(rng).unwrap()
         } else {
-            -*GOLD_ADJST_LESS.choose(rng).unwrap()
+            -*less.choose(rng).unwrap()
         };
         coefficients.push(ratio);
     }
@@ -0,0 +1,183 @@
+use serde::{Deserialize, Serialize};
+
+/// 相邻两浪幅度之比的允许范围
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct RatioBounds {
+    pub min: f64, // 最小比值
+    pub max: f64, // 最大比值
+}
+
+/// 浪幅（相邻转折点之间的距离）的分布
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AmplitudeStats {
+    pub min: f64,     // 最小浪幅
+    pub max: f64,     // 最大浪幅
+    pub mean: f64,    // 平均浪幅
+    pub std_dev: f64, // 浪幅标准差
+    pub p50: f64,     // 中位数
+    pub p90: f64,     // 90 分位数
+}
+
+/// 波浪的质量问题
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum WaveIssue {
+    Empty,                                   // 波浪为空
+    NonFinite(usize),                        // 含有 NaN 或无穷大的点数，通常来自系数之和为 0
+    Flat,                                    // 所有点都与池底相同
+    ZeroLengthSegments(usize),               // 长度为 0 的分段数
+    AboveBoundary(f64),                      // 超出边界线的最大距离
+    BelowBaseline(f64),                      // 低于底线的最大距离
+    RatioOutOfBounds { min: f64, max: f64 }, // 超出允许范围的浪幅比值
+}
+
+/// 波浪的统计分析结果
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct WaveAnalysis {
+    pub points: usize,             // 分段目标数
+    pub turning_points: usize,     // 转折点数
+    pub amplitude: AmplitudeStats, // 浪幅分布
+    pub mean_segment_len: f64,     // 平均分段长度
+    pub max_above_boundary: f64,   // 超出边界线的最大距离
+    pub max_below_baseline: f64,   // 低于底线的最大距离
+    pub ratio_min: f64,            // 相邻浪幅之比的最小值
+    pub ratio_max: f64,            // 相邻浪幅之比的最大值
+    pub issues: Vec<WaveIssue>,    // 发现的问题
+}
+
+impl WaveAnalysis {
+    /// 波浪为空、含有非有限值或完全平坦，Pool 无法据此正常分段
+    pub fn is_degenerate(&self) -> bool {
+        self.issues.iter().any(|issue| {
+            matches!(
+                issue,
+                WaveIssue::Empty | WaveIssue::NonFinite(_) | WaveIssue::Flat
+            )
+        })
+    }
+
+    pub fn is_ok(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+/// 分析从 `pot` 出发的一条波浪，`bounds` 为 None 时不检查浪幅比值
+pub fn analyze(
+    wave: &[u64],
+    pot: u64,
+    baseline: u64,
+    boundary: u64,
+    bounds: Option<RatioBounds>,
+) -> WaveAnalysis {
+    let wave: Vec<f64> = wave.iter().map(|&v| v as f64).collect();
+    analyze_raw(&wave, pot, baseline, boundary, bounds)
+}
+
+/// 分析未取整的波浪，可以发现取整后会变成 0 的 NaN
+pub fn analyze_raw(
+    wave: &[f64],
+    pot: u64,
+    baseline: u64,
+    boundary: u64,
+    bounds: Option<RatioBounds>,
+) -> WaveAnalysis {
+    let mut analysis = WaveAnalysis {
+        points: wave.len(),
+        ..Default::default()
+    };
+    if wave.is_empty() {
+        analysis.issues.push(WaveIssue::Empty);
+        return analysis;
+    }
+    let non_finite = wave.iter().filter(|v| !v.is_finite()).count();
+    if non_finite > 0 {
+        analysis.issues.push(WaveIssue::NonFinite(non_finite));
+    }
+
+    let points: Vec<f64> = std::iter::once(pot as f64)
+        .chain(wave.iter().cloned().filter(|v| v.is_finite()))
+        .collect();
+    let steps: Vec<f64> = points.windows(2).map(|w| w[1] - w[0]).collect();
+    if steps.iter().all(|&step| step == 0.0) {
+        analysis.issues.push(WaveIssue::Flat);
+        return analysis;
+    }
+    let zero = steps.iter().filter(|&&step| step == 0.0).count();
+    if zero > 0 {
+        analysis.issues.push(WaveIssue::ZeroLengthSegments(zero));
+    }
+    analysis.mean_segment_len = steps.iter().map(|s| s.abs()).sum::<f64>() / steps.len() as f64;
+
+    let highest = points.iter().cloned().fold(f64::MIN, f64::max);
+    let lowest = points.iter().cloned().fold(f64::MAX, f64::min);
+    analysis.max_above_boundary = (highest - boundary as f64).max(0.0);
+    analysis.max_below_baseline = (baseline as f64 - lowest).max(0.0);
+    if analysis.max_above_boundary > 0.0 {
+        analysis
+            .issues
+            .push(WaveIssue::AboveBoundary(analysis.max_above_boundary));
+    }
+    if analysis.max_below_baseline > 0.0 {
+        analysis
+            .issues
+            .push(WaveIssue::BelowBaseline(analysis.max_below_baseline));
+    }
+
+    let swings = swings(&steps);
+    analysis.turning_points = swings.len().saturating_sub(1);
+    analysis.amplitude = amplitude_stats(&swings);
+
+    let ratios: Vec<f64> = swings.windows(2).map(|w| w[1] / w[0]).collect();
+    if !ratios.is_empty() {
+        analysis.ratio_min = ratios.iter().cloned().fold(f64::INFINITY, f64::min);
+        analysis.ratio_max = ratios.iter().cloned().fold(0.0, f64::max);
+    }
+    if let Some(bounds) = bounds {
+        if !ratios.is_empty()
+            && (analysis.ratio_min < bounds.min || analysis.ratio_max > bounds.max)
+        {
+            analysis.issues.push(WaveIssue::RatioOutOfBounds {
+                min: analysis.ratio_min,
+                max: analysis.ratio_max,
+            });
+        }
+    }
+    analysis
+}
+
+/// 将同方向的连续分段合并为浪，返回各浪的幅度
+fn swings(steps: &[f64]) -> Vec<f64> {
+    let mut swings: Vec<f64> = Vec::new();
+    let mut last = 0.0;
+    for &step in steps.iter().filter(|&&step| step != 0.0) {
+        match swings.last_mut() {
+            Some(swing) if step.signum() == last => *swing += step.abs(),
+            _ => swings.push(step.abs()),
+        }
+        last = step.signum();
+    }
+    swings
+}
+
+fn amplitude_stats(swings: &[f64]) -> AmplitudeStats {
+    if swings.is_empty() {
+        return AmplitudeStats::default();
+    }
+    let mut sorted = swings.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    let n = sorted.len() as f64;
+    let mean = sorted.iter().sum::<f64>() / n;
+    let variance = if sorted.len() < 2 {
+        0.0
+    } else {
+        sorted.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (n - 1.0)
+    };
+    let percentile = |p: f64| sorted[((n - 1.0) * p).round() as usize];
+    AmplitudeStats {
+        min: sorted[0],
+        max: sorted[sorted.len() - 1],
+        mean,
+        std_dev: variance.sqrt(),
+        p50: percentile(0.5),
+        p90: percentile(0.9),
+    }
+}
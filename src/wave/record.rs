@@ -0,0 +1,152 @@
+use std::fmt::Write;
+
+use rand::SeedableRng;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::{PoolError, Result},
+    generators::WaveConfig,
+    pool::PoolRng,
+};
+
+/// 可导出和导入的波浪，包含生成时的池底、底线、边界线、生成器配置及种子
+///
+/// 手工设计的波浪只需要填写 `targets`，其余字段仅作记录
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct WaveRecord {
+    #[serde(default)]
+    pub pot: u64, // 生成时的池底
+    #[serde(default)]
+    pub base_line: u64, // 底线
+    #[serde(default)]
+    pub boundary: u64, // 边界线
+    #[serde(default)]
+    pub seed: Option<u64>, // 随机种子
+    #[serde(default)]
+    pub wave: Option<WaveConfig>, // 生成器配置
+    pub targets: Vec<u64>, // 分段目标
+}
+
+impl WaveRecord {
+    /// 按配置和种子生成一条波浪，相同参数总是得到相同的波浪
+    pub fn generate(
+        wave: &WaveConfig,
+        pot: u64,
+        base_line: u64,
+        boundary: u64,
+        seed: u64,
+    ) -> Result<Self> {
+        let generator = wave.build()?;
+        let mut rng = PoolRng::seed_from_u64(seed);
+        let targets = generator.generate(pot, base_line, boundary, &mut rng)?;
+        Ok(WaveRecord {
+            pot,
+            base_line,
+            boundary,
+            seed: Some(seed),
+            wave: Some(wave.clone()),
+            targets,
+        })
+    }
+
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string_pretty(self).map_err(|e| PoolError::InvalidWaveData(e.to_string()))
+    }
+
+    pub fn from_json(json: &str) -> Result<Self> {
+        serde_json::from_str(json).map_err(|e| PoolError::InvalidWaveData(e.to_string()))
+    }
+
+    /// 导出为 CSV，参数写在 `#` 开头的注释行中，数据为 `index,target` 两列
+    pub fn to_csv(&self) -> Result<String> {
+        let mut csv = String::new();
+        let _ = writeln!(csv, "# pot={}", self.pot);
+        let _ = writeln!(csv, "# base_line={}", self.base_line);
+        let _ = writeln!(csv, "# boundary={}", self.boundary);
+        if let Some(seed) = self.seed {
+            let _ = writeln!(csv, "# seed={}", seed);
+        }
+        if let Some(wave) = &self.wave {
+            let json = serde_json::to_string(wave)
+                .map_err(|e| PoolError::InvalidWaveData(e.to_string()))?;
+            let _ = writeln!(csv, "# wave={}", json);
+        }
+        csv.push_str("index,target\n");
+        for (index, target) in self.targets.iter().enumerate() {
+            let _ = writeln!(csv, "{},{}", index, target);
+        }
+        Ok(csv)
+    }
+
+    /// 从 CSV 导入，兼容电子表格导出的文件
+    ///
+    /// 可以只有一列目标值，也可以有多列并以表头中的 `target` 列为准；小数会四舍五入，
+    /// `#` 开头的注释行按 `key=value` 读取参数，不认识的参数忽略
+    pub fn from_csv(csv: &str) -> Result<Self> {
+        let mut record = WaveRecord::default();
+        let mut column = None;
+        for (line_no, line) in csv.lines().enumerate() {
+            let line = line.trim().trim_start_matches('\u{feff}');
+            if line.is_empty() {
+                continue;
+            }
+            if let Some(meta) = line.strip_prefix('#') {
+                record.parse_meta(meta, line_no + 1)?;
+                continue;
+            }
+            let cells: Vec<&str> = line.split(',').map(|cell| cell.trim()).collect();
+            let index = *column.get_or_insert_with(|| {
+                cells
+                    .iter()
+                    .position(|cell| cell.eq_ignore_ascii_case("target"))
+                    .unwrap_or(cells.len().min(2) - 1)
+            });
+            let cell = cells.get(index).copied().unwrap_or("");
+            match parse_target(cell) {
+                Some(target) => record.targets.push(target),
+                // 第一行数据无法解析时视为表头
+                None if record.targets.is_empty()
+                    && cells.iter().any(|c| parse_target(c).is_none()) => {}
+                None => {
+                    return Err(PoolError::InvalidWaveData(format!(
+                        "line {}: invalid target {:?}",
+                        line_no + 1,
+                        cell
+                    )))
+                }
+            }
+        }
+        Ok(record)
+    }
+
+    fn parse_meta(&mut self, meta: &str, line_no: usize) -> Result<()> {
+        let Some((key, value)) = meta.split_once('=') else {
+            return Ok(());
+        };
+        let value = value.trim();
+        let invalid = |e: &dyn std::fmt::Display| {
+            PoolError::InvalidWaveData(format!("line {}: {}", line_no, e))
+        };
+        match key.trim() {
+            "pot" => self.pot = value.parse().map_err(|e| invalid(&e))?,
+            "base_line" => self.base_line = value.parse().map_err(|e| invalid(&e))?,
+            "boundary" => self.boundary = value.parse().map_err(|e| invalid(&e))?,
+            "seed" => self.seed = Some(value.parse().map_err(|e| invalid(&e))?),
+            "wave" => self.wave = Some(serde_json::from_str(value).map_err(|e| invalid(&e))?),
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+fn parse_target(cell: &str) -> Option<u64> {
+    if let Ok(target) = cell.parse::<u64>() {
+        return Some(target);
+    }
+    match cell.parse::<f64>() {
+        Ok(value) if value.is_finite() && value >= 0.0 && value <= u64::MAX as f64 => {
+            Some(value.round() as u64)
+        }
+        _ => None,
+    }
+}